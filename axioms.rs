@@ -0,0 +1,165 @@
+// axioms.rs
+// Axiom Lock (axioms.lock)
+// Version: v1
+//
+// The expected axiom set is data: axioms.lock maps every file in the axiom
+// directory to its hash. The verifier pins only the hash of the lock itself.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::hash::{Algorithm, Digest, Hasher};
+
+pub const AXIOMS_DIR: &str = "axioms";
+pub const LOCK_FILE: &str = "axioms.lock";
+pub const LOCK_SCHEMA: &str = "scxq7://axioms-lock/v1";
+pub const LOCK_HASH: Algorithm = Algorithm::Sha256;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pinned,
+    Mismatch,
+    Missing,
+    Unpinned,
+}
+
+#[derive(Debug, Clone)]
+pub struct AxiomLock {
+    pub axioms: BTreeMap<String, Digest>,
+}
+
+/// Searches `start` and its ancestors for an `axioms/` directory. A file
+/// target starts the search from its parent.
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    let start = fs::canonicalize(start).ok()?;
+    let start = if start.is_dir() {
+        start.as_path()
+    } else {
+        start.parent()?
+    };
+    start
+        .ancestors()
+        .map(|dir| dir.join(AXIOMS_DIR))
+        .find(|candidate| candidate.is_dir())
+}
+
+/// Hashes every regular file in `dir` except the lock itself, keyed by file name.
+pub fn scan(dir: &Path) -> Result<BTreeMap<String, Digest>, String> {
+    let entries = fs::read_dir(dir).map_err(|_| "axioms missing".to_string())?;
+    let mut found = BTreeMap::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !path.is_file() || name == LOCK_FILE {
+            continue;
+        }
+        let bytes = fs::read(&path).map_err(|_| "axioms unreadable".to_string())?;
+        found.insert(name.to_string(), Digest::of(LOCK_HASH, &bytes));
+    }
+    Ok(found)
+}
+
+impl AxiomLock {
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        let doc: Value = serde_json::from_slice(bytes)
+            .map_err(|_| "axioms.lock is not valid JSON".to_string())?;
+        if doc.get("@schema").and_then(Value::as_str) != Some(LOCK_SCHEMA) {
+            return Err("axioms.lock has wrong @schema".to_string());
+        }
+        let table = doc
+            .get("axioms")
+            .and_then(Value::as_object)
+            .ok_or_else(|| "axioms.lock missing axioms".to_string())?;
+        let mut axioms = BTreeMap::new();
+        for (name, hash) in table {
+            let hash = hash
+                .as_str()
+                .ok_or_else(|| format!("axioms.lock: hash for {} is not a string", name))?;
+            let digest =
+                Digest::parse(hash).map_err(|e| format!("axioms.lock: {} ({})", e, name))?;
+            digest
+                .expect(LOCK_HASH)
+                .map_err(|e| format!("axioms.lock: {} ({})", e, name))?;
+            axioms.insert(name.clone(), digest);
+        }
+        Ok(AxiomLock { axioms })
+    }
+
+    pub fn from_scan(found: BTreeMap<String, Digest>) -> Self {
+        AxiomLock { axioms: found }
+    }
+
+    /// Canonical lock text: sorted names, two-space indent, trailing newline.
+    pub fn render(&self) -> String {
+        let mut out = format!("{{\n  \"@schema\": \"{}\",\n  \"axioms\": {{", LOCK_SCHEMA);
+        for (i, (name, hash)) in self.axioms.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&format!("\n    \"{}\": \"{}\"", name, hash));
+        }
+        out.push_str("\n  }\n}\n");
+        out
+    }
+
+    /// Lock entries and unlocked files with their status, sorted by name.
+    pub fn statuses(&self, found: &BTreeMap<String, Digest>) -> Vec<(String, Status)> {
+        let mut names: Vec<&String> = self.axioms.keys().chain(found.keys()).collect();
+        names.sort();
+        names.dedup();
+        names
+            .into_iter()
+            .map(|name| {
+                let status = match (self.axioms.get(name), found.get(name)) {
+                    (Some(expected), Some(actual)) if expected == actual => Status::Pinned,
+                    (Some(_), Some(_)) => Status::Mismatch,
+                    (Some(_), None) => Status::Missing,
+                    (None, _) => Status::Unpinned,
+                };
+                (name.clone(), status)
+            })
+            .collect()
+    }
+}
+
+/// Reads the lock from `dir`, checks it against the pinned lock hash, then checks
+/// every axiom file. Any file not covered by the lock is a failure.
+pub fn verify(dir: &Path, pinned_lock_hash: &str) -> Result<AxiomLock, String> {
+    let lock_bytes =
+        fs::read(dir.join(LOCK_FILE)).map_err(|_| "axioms.lock missing".to_string())?;
+    Digest::check(pinned_lock_hash, LOCK_HASH, &lock_bytes)
+        .map_err(|reason| format!("axioms.lock {}", reason))?;
+    let lock = AxiomLock::parse(&lock_bytes)?;
+    let found = scan(dir)?;
+    for (name, status) in lock.statuses(&found) {
+        match status {
+            Status::Pinned => {}
+            Status::Mismatch => return Err(format!("axiom hash mismatch ({})", name)),
+            Status::Missing => return Err(format!("axioms missing ({})", name)),
+            Status::Unpinned => return Err(format!("unpinned axiom ({})", name)),
+        }
+    }
+    Ok(lock)
+}
+
+/// One hash over the whole axiom directory, lock included: sha256 over
+/// `name \0 hex(sha256(file)) \0` for every file in name order.
+pub fn combined_hash(dir: &Path) -> Result<Digest, String> {
+    let mut found = scan(dir)?;
+    let lock_bytes =
+        fs::read(dir.join(LOCK_FILE)).map_err(|_| "axioms.lock missing".to_string())?;
+    found.insert(LOCK_FILE.to_string(), Digest::of(LOCK_HASH, &lock_bytes));
+    let mut hasher = Hasher::new(LOCK_HASH);
+    for (name, hash) in &found {
+        hasher.update(name.as_bytes());
+        hasher.update(b"\0");
+        hasher.update(hash.hex().as_bytes());
+        hasher.update(b"\0");
+    }
+    Ok(hasher.finalize())
+}
@@ -0,0 +1,213 @@
+// binary_split.rs
+// Binary Splitting Kernel (binary_split)
+// Version: v1
+//
+// Exact evaluator for S(N) = Σ_{k=1..N} k·2^k·(k!)^2/(2k)! → π + 3, per
+// docs/pi-series-identity.md §6. Terms follow T_k = T_{k-1}·p(k)/q(k) with
+// p(k) = k², q(k) = (k-1)(2k-1) and T_1 = 1. The recursion runs over closed
+// intervals [a, b] with integer triples (P, Q, T); nothing is divided until
+// the single collapse.
+//
+// The recursion tree is also emitted as a CM-1 stream in the shape gated by
+// docs/cm1-binary-splitting-gate-v1.md: SOH STX, then one SO … SI scope per
+// subtree of the root with one RS per subdivision inside it, then ETX EOT.
+
+use num_bigint::{BigInt, BigUint};
+use num_traits::One;
+
+use crate::exact;
+use crate::scxq2_pack;
+
+pub const KERNEL_ID: &str = "binary_split";
+pub const SERIES: &str = "Σ_{k=1..∞} k·2^k·(k!)^2 / (2k)!";
+/// S(N) falls short of π + 3 by the tail; the residue is 3.
+pub const RESIDUE: i64 = 3;
+
+pub const SOH: u8 = 0x01;
+pub const STX: u8 = 0x02;
+pub const ETX: u8 = 0x03;
+pub const EOT: u8 = 0x04;
+pub const SO: u8 = 0x0E;
+pub const SI: u8 = 0x0F;
+pub const RS: u8 = 0x1E;
+
+/// Integer triple for a closed interval [a, b]: Σ_{k=a..b} Π_{j=a..k} p(j)/q(j) = T/Q,
+/// and P/Q = Π_{j=a..b} p(j)/q(j).
+#[derive(Debug, Clone)]
+struct Triple {
+    p: BigInt,
+    q: BigInt,
+    t: BigInt,
+}
+
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    pub terms: u64,
+    /// Exact partial sum S(terms) = numerator / denominator, not reduced.
+    pub numerator: BigInt,
+    pub denominator: BigUint,
+    pub cm1: Vec<u8>,
+}
+
+fn p(k: u64) -> BigInt {
+    if k == 1 {
+        BigInt::one()
+    } else {
+        BigInt::from(k) * BigInt::from(k)
+    }
+}
+
+fn q(k: u64) -> BigInt {
+    if k == 1 {
+        BigInt::one()
+    } else {
+        BigInt::from(k - 1) * BigInt::from(2 * k - 1)
+    }
+}
+
+fn split_point(a: u64, b: u64) -> u64 {
+    a + (b - a) / 2
+}
+
+/// P/Q/T recursion over [a, b]; the merge is T = T_L·Q_R + P_L·T_R.
+fn split(a: u64, b: u64) -> Triple {
+    if a == b {
+        let p = p(a);
+        return Triple {
+            t: p.clone(),
+            p,
+            q: q(a),
+        };
+    }
+    let m = split_point(a, b);
+    let left = split(a, m);
+    let right = split(m + 1, b);
+    Triple {
+        t: &left.t * &right.q + &left.p * &right.t,
+        p: left.p * right.p,
+        q: left.q * right.q,
+    }
+}
+
+/// Subdivisions inside [a, b]: one per internal node of its subtree.
+fn subdivisions(a: u64, b: u64) -> u64 {
+    b - a
+}
+
+/// The CM-1 control stream for the recursion tree over [1, terms].
+pub fn control_stream(terms: u64) -> Vec<u8> {
+    let mut out = vec![SOH, STX];
+    if terms > 1 {
+        let m = split_point(1, terms);
+        for (a, b) in [(1, m), (m + 1, terms)] {
+            out.push(SO);
+            out.extend(std::iter::repeat_n(RS, subdivisions(a, b) as usize));
+            out.push(SI);
+        }
+    }
+    out.extend([ETX, EOT]);
+    out
+}
+
+/// The sealed binary-split gate automaton (Idle → Header → Active ⇄ Subscope
+/// → Closing → Collapse). Any other transition is illegal.
+pub fn gate(stream: &[u8]) -> Result<(), String> {
+    #[derive(PartialEq)]
+    enum State {
+        Idle,
+        Header,
+        Active,
+        Subscope,
+        Closing,
+        Collapse,
+    }
+    let mut state = State::Idle;
+    for (offset, byte) in stream.iter().copied().enumerate() {
+        state = match (state, byte) {
+            (State::Idle, SOH) => State::Header,
+            (State::Header, STX) => State::Active,
+            (State::Active, SO) => State::Subscope,
+            (State::Active, ETX) => State::Closing,
+            (State::Subscope, RS) => State::Subscope,
+            (State::Subscope, SI) => State::Active,
+            (State::Closing, EOT) => State::Collapse,
+            _ => {
+                return Err(format!(
+                    "binary-split gate: illegal 0x{:02X} at offset {}",
+                    byte, offset
+                ))
+            }
+        };
+    }
+    if state != State::Collapse {
+        return Err("binary-split gate: stream ends before collapse".to_string());
+    }
+    Ok(())
+}
+
+/// Terms needed so the tail of the series is below 2^-bits. For k ≥ 10,
+/// T_k ≤ 2·k^1.5/2^k and the term ratio is below 0.56, so the tail after N
+/// is at most 8·(N+1)²/2^(N+1).
+pub fn terms_for_bits(bits: u64) -> u64 {
+    let mut n = bits.max(10);
+    loop {
+        let log2_square = 2 * (64 - (n + 1).leading_zeros() as u64);
+        if n + 1 >= bits + 3 + log2_square {
+            return n;
+        }
+        n += 1;
+    }
+}
+
+/// Evaluates S(terms) exactly and emits the matching control stream.
+pub fn evaluate(terms: u64) -> Result<Evaluation, String> {
+    if terms == 0 {
+        return Err("binary_split needs at least one term".to_string());
+    }
+    let triple = split(1, terms);
+    let cm1 = control_stream(terms);
+    gate(&cm1)?;
+    Ok(Evaluation {
+        terms,
+        numerator: triple.t,
+        denominator: triple.q.magnitude().clone(),
+        cm1,
+    })
+}
+
+impl Evaluation {
+    /// The single collapse: S truncated to `digits` decimal places.
+    pub fn decimal(&self, digits: usize) -> String {
+        exact::decimal(&self.numerator, &self.denominator, digits)
+    }
+
+    /// S − 3, the geometric mass (→ π), truncated to `digits` decimal places.
+    pub fn geometric_decimal(&self, digits: usize) -> String {
+        let residue = BigInt::from(RESIDUE) * BigInt::from(self.denominator.clone());
+        exact::decimal(&(&self.numerator - residue), &self.denominator, digits)
+    }
+
+    /// S projected to f64 with one correctly rounded division.
+    pub fn value(&self) -> Result<f64, String> {
+        exact::project(&self.numerator, &self.denominator)
+    }
+
+    /// Canonical result text: the exact collapse to `digits` places, its f64
+    /// projection and the control stream that gates it.
+    pub fn render(&self, digits: usize) -> Result<String, String> {
+        let value = self.value()?;
+        Ok(format!(
+            "{{\n  \"kernel\": \"{}\",\n  \"series\": \"{}\",\n  \"interval\": [1, {}],\n  \"digits\": {},\n  \"sum\": \"{}\",\n  \"geometric\": \"{}\",\n  \"residue\": {},\n  \"f64\": {{\"bits\":\"0x{:016x}\",\"decimal\":\"{:?}\"}},\n  \"cm1\": \"{}\"\n}}\n",
+            KERNEL_ID,
+            SERIES,
+            self.terms,
+            digits,
+            self.decimal(digits),
+            self.geometric_decimal(digits),
+            RESIDUE,
+            value.to_bits(),
+            value,
+            scxq2_pack::render_hex(&self.cm1)
+        ))
+    }
+}
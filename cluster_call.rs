@@ -0,0 +1,526 @@
+// cluster_call.rs
+// Cluster-Call Documents (cluster-call.xsd v1)
+// Version: v1
+//
+// Reader for <cluster-call> documents (docs/cluster-call-spec-v1.md) and their
+// cross-check against the TOML axioms that permit them. The reader enforces
+// cluster-call.xsd: required target and kernel, optional domain, timeout_ms,
+// mode (sync | async) and air_gap, then inputs?, outputs, constraints?, proof?
+// in that order. Elements are in the urn:scxq7:cluster-call:v1 namespace or,
+// as in the spec examples, in no namespace.
+//
+// The TOML side is the [axiom.clusters.<name>] tables of a
+// pi-gccp.axioms.toml-style file. XML is subordinate to it: the target must be
+// a declared cluster, the kernel one of its allowed_kernels, a domain one of
+// its allowed_domains, and constraints may only tighten the declaration
+// (deterministic = true, side_effects = false and memory_mb stay binding). An
+// air-gapped call needs an air_gapped, deterministic cluster and a required
+// proof.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use roxmltree::{Document, Node};
+
+pub const CLUSTER_CALL_NAMESPACE: &str = "urn:scxq7:cluster-call:v1";
+pub const CLUSTER_CALL_TYPE: &str = "cluster-call";
+pub const CLUSTER_AXIOMS_FILE: &str = "pi-gccp.axioms.toml";
+
+const CALL_ATTRIBUTES: [&str; 6] = [
+    "target",
+    "kernel",
+    "domain",
+    "timeout_ms",
+    "mode",
+    "air_gap",
+];
+const IO_ATTRIBUTES: [&str; 3] = ["ref", "format", "hash"];
+const CLUSTER_FIELDS: [&str; 11] = [
+    "type",
+    "mode",
+    "side_effects",
+    "deterministic",
+    "memory_mb",
+    "allowed_kernels",
+    "allowed_domains",
+    "air_gapped",
+    "network",
+    "inputs",
+    "outputs",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Sync,
+    Async,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IoRef {
+    pub reference: String,
+    pub format: Option<String>,
+    pub hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Constraints {
+    pub deterministic: Option<bool>,
+    pub side_effects: Option<bool>,
+    pub memory_mb: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofSpec {
+    pub require: bool,
+    pub scheme: Option<String>,
+    pub bind: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterCall {
+    pub target: String,
+    pub kernel: String,
+    pub domain: Option<String>,
+    pub timeout_ms: Option<u64>,
+    pub mode: Mode,
+    pub air_gap: bool,
+    pub inputs: Vec<IoRef>,
+    pub outputs: Vec<IoRef>,
+    pub constraints: Constraints,
+    pub proof: Option<ProofSpec>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster {
+    pub name: String,
+    pub deterministic: Option<bool>,
+    pub side_effects: Option<bool>,
+    pub memory_mb: Option<u64>,
+    pub allowed_kernels: Vec<String>,
+    pub allowed_domains: Vec<String>,
+    pub air_gapped: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct ClusterAxioms {
+    pub path: PathBuf,
+    pub clusters: BTreeMap<String, Cluster>,
+}
+
+/// True for `.xml` files whose root element is <cluster-call>, unqualified or
+/// in the cluster-call namespace.
+pub fn is_cluster_call(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("xml")
+        && fs::read_to_string(path)
+            .ok()
+            .and_then(|text| {
+                Document::parse(&text)
+                    .ok()
+                    .map(|doc| is_call_root(doc.root_element()))
+            })
+            .unwrap_or(false)
+}
+
+fn is_call_root(root: Node) -> bool {
+    root.tag_name().name() == CLUSTER_CALL_TYPE
+        && matches!(
+            root.tag_name().namespace(),
+            None | Some(CLUSTER_CALL_NAMESPACE)
+        )
+}
+
+/// Nearest `pi-gccp.axioms.toml` in the directory of `start` or above.
+pub fn discover_axioms(start: &Path) -> Option<PathBuf> {
+    let start = fs::canonicalize(start).ok()?;
+    let start = if start.is_dir() {
+        start.as_path()
+    } else {
+        start.parent()?
+    };
+    start
+        .ancestors()
+        .map(|dir| dir.join(CLUSTER_AXIOMS_FILE))
+        .find(|candidate| candidate.is_file())
+}
+
+/// xs:boolean.
+fn boolean(text: &str, path: &str) -> Result<bool, String> {
+    match text.trim() {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => Err(format!("{}: must be a boolean", path)),
+    }
+}
+
+/// xs:nonNegativeInteger.
+fn count(text: &str, path: &str) -> Result<u64, String> {
+    let digits = text.trim();
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("{}: must be a non-negative integer", path));
+    }
+    digits
+        .parse()
+        .map_err(|_| format!("{}: out of range", path))
+}
+
+fn check_attributes(node: Node, path: &str, allowed: &[&str]) -> Result<(), String> {
+    for attribute in node.attributes() {
+        if attribute.namespace().is_some() || !allowed.contains(&attribute.name()) {
+            return Err(format!("{}/@{}: unknown attribute", path, attribute.name()));
+        }
+    }
+    Ok(())
+}
+
+/// Element children in the document namespace, rejecting stray text.
+fn children<'a, 'i>(
+    node: Node<'a, 'i>,
+    namespace: Option<&str>,
+    path: &str,
+) -> Result<Vec<Node<'a, 'i>>, String> {
+    let mut elements = Vec::new();
+    for child in node.children() {
+        if child.is_element() {
+            if child.tag_name().namespace() != namespace {
+                return Err(format!(
+                    "{}/{}: wrong namespace",
+                    path,
+                    child.tag_name().name()
+                ));
+            }
+            elements.push(child);
+        } else if child.is_text() && !child.text().unwrap_or("").trim().is_empty() {
+            return Err(format!("{}: text is not allowed here", path));
+        }
+    }
+    Ok(elements)
+}
+
+/// Text of a simple element with no attributes or children.
+fn simple(node: Node, path: &str) -> Result<String, String> {
+    check_attributes(node, path, &[])?;
+    if node.children().any(|c| c.is_element()) {
+        return Err(format!("{}: must be text", path));
+    }
+    Ok(node.text().unwrap_or("").to_string())
+}
+
+/// Splits `elements` into the XSD sequence `names`: each name at most once,
+/// in order. `required` names must be present.
+fn sequence<'a, 'i>(
+    elements: Vec<Node<'a, 'i>>,
+    names: &[&str],
+    required: &[&str],
+    path: &str,
+) -> Result<BTreeMap<String, Node<'a, 'i>>, String> {
+    let mut found = BTreeMap::new();
+    let mut position = 0;
+    for element in elements {
+        let name = element.tag_name().name();
+        match names[position..].iter().position(|n| *n == name) {
+            Some(offset) => {
+                position += offset + 1;
+                found.insert(name.to_string(), element);
+            }
+            None => return Err(format!("{}/{}: unexpected element", path, name)),
+        }
+    }
+    for name in required {
+        if !found.contains_key(*name) {
+            return Err(format!("{}/{}: missing", path, name));
+        }
+    }
+    Ok(found)
+}
+
+fn io_list(
+    node: Node,
+    namespace: Option<&str>,
+    path: &str,
+    item: &str,
+) -> Result<Vec<IoRef>, String> {
+    check_attributes(node, path, &[])?;
+    let items = children(node, namespace, path)?;
+    if items.is_empty() {
+        return Err(format!("{}/{}: missing", path, item));
+    }
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let here = format!("{}/{}[{}]", path, item, i + 1);
+            if entry.tag_name().name() != item {
+                return Err(format!("{}: unexpected element", here));
+            }
+            check_attributes(entry, &here, &IO_ATTRIBUTES)?;
+            if !children(entry, namespace, &here)?.is_empty() {
+                return Err(format!("{}: must be empty", here));
+            }
+            Ok(IoRef {
+                reference: entry
+                    .attribute("ref")
+                    .ok_or_else(|| format!("{}/@ref: missing", here))?
+                    .to_string(),
+                format: entry.attribute("format").map(str::to_string),
+                hash: entry.attribute("hash").map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+impl ClusterCall {
+    /// Reads a <cluster-call>, enforcing cluster-call.xsd.
+    pub fn parse(bytes: &[u8]) -> Result<ClusterCall, String> {
+        let text =
+            std::str::from_utf8(bytes).map_err(|_| "cluster-call is not UTF-8".to_string())?;
+        let doc = Document::parse(text).map_err(|e| format!("invalid XML ({})", e))?;
+        let root = doc.root_element();
+        let namespace = root.tag_name().namespace();
+        if !is_call_root(root) {
+            return Err(format!(
+                "root must be cluster-call in the {} namespace or none",
+                CLUSTER_CALL_NAMESPACE
+            ));
+        }
+        let path = "/cluster-call";
+        check_attributes(root, path, &CALL_ATTRIBUTES)?;
+        let attribute = |name: &str| root.attribute(name).map(str::to_string);
+        let target = attribute("target").ok_or("/cluster-call/@target: missing")?;
+        let kernel = attribute("kernel").ok_or("/cluster-call/@kernel: missing")?;
+        let timeout_ms = match attribute("timeout_ms") {
+            None => None,
+            Some(value) => Some(count(&value, "/cluster-call/@timeout_ms")?),
+        };
+        let mode = match attribute("mode").as_deref() {
+            None | Some("sync") => Mode::Sync,
+            Some("async") => Mode::Async,
+            Some(_) => return Err("/cluster-call/@mode: must be sync or async".to_string()),
+        };
+        let air_gap = match attribute("air_gap") {
+            None => false,
+            Some(value) => boolean(&value, "/cluster-call/@air_gap")?,
+        };
+
+        let parts = sequence(
+            children(root, namespace, path)?,
+            &["inputs", "outputs", "constraints", "proof"],
+            &["outputs"],
+            path,
+        )?;
+        let inputs = match parts.get("inputs") {
+            None => Vec::new(),
+            Some(node) => io_list(*node, namespace, "/cluster-call/inputs", "input")?,
+        };
+        let outputs = io_list(
+            parts["outputs"],
+            namespace,
+            "/cluster-call/outputs",
+            "output",
+        )?;
+
+        let mut constraints = Constraints::default();
+        if let Some(node) = parts.get("constraints") {
+            let here = "/cluster-call/constraints";
+            check_attributes(*node, here, &[])?;
+            let fields = sequence(
+                children(*node, namespace, here)?,
+                &["deterministic", "side_effects", "memory_mb"],
+                &[],
+                here,
+            )?;
+            for (name, field) in &fields {
+                let at = format!("{}/{}", here, name);
+                let value = simple(*field, &at)?;
+                match name.as_str() {
+                    "deterministic" => constraints.deterministic = Some(boolean(&value, &at)?),
+                    "side_effects" => constraints.side_effects = Some(boolean(&value, &at)?),
+                    _ => constraints.memory_mb = Some(count(&value, &at)?),
+                }
+            }
+        }
+
+        let proof = match parts.get("proof") {
+            None => None,
+            Some(node) => {
+                let here = "/cluster-call/proof";
+                check_attributes(*node, here, &[])?;
+                let fields = sequence(
+                    children(*node, namespace, here)?,
+                    &["require", "scheme", "bind"],
+                    &["require"],
+                    here,
+                )?;
+                let text = |name: &str| -> Result<Option<String>, String> {
+                    fields
+                        .get(name)
+                        .map(|field| simple(*field, &format!("{}/{}", here, name)))
+                        .transpose()
+                };
+                Some(ProofSpec {
+                    require: boolean(
+                        &text("require")?.unwrap_or_default(),
+                        "/cluster-call/proof/require",
+                    )?,
+                    scheme: text("scheme")?,
+                    bind: text("bind")?,
+                })
+            }
+        };
+
+        Ok(ClusterCall {
+            target,
+            kernel,
+            domain: attribute("domain"),
+            timeout_ms,
+            mode,
+            air_gap,
+            inputs,
+            outputs,
+            constraints,
+            proof,
+        })
+    }
+
+    /// Checks the call against the clusters the TOML axioms permit. The error
+    /// names the first undeclared binding or widened constraint.
+    pub fn check(&self, axioms: &ClusterAxioms) -> Result<(), String> {
+        let origin = axioms.path.display();
+        let cluster = axioms.clusters.get(&self.target).ok_or_else(|| {
+            format!(
+                "target {} is not a declared cluster ({})",
+                self.target, origin
+            )
+        })?;
+        let table = format!("[axiom.clusters.{}]", cluster.name);
+        if !cluster.allowed_kernels.contains(&self.kernel) {
+            return Err(format!(
+                "kernel {} is not in {} allowed_kernels ({})",
+                self.kernel, table, origin
+            ));
+        }
+        if let Some(domain) = &self.domain {
+            if !cluster.allowed_domains.contains(domain) {
+                return Err(format!(
+                    "domain {} is not in {} allowed_domains ({})",
+                    domain, table, origin
+                ));
+            }
+        }
+
+        let widened = |field: &str, xml: String, toml: String| {
+            Err(format!(
+                "constraints/{} = {} widens {} {} = {} ({})",
+                field, xml, table, field, toml, origin
+            ))
+        };
+        if let (Some(true), Some(false)) = (cluster.deterministic, self.constraints.deterministic) {
+            return widened("deterministic", "false".into(), "true".into());
+        }
+        if let (Some(false), Some(true)) = (cluster.side_effects, self.constraints.side_effects) {
+            return widened("side_effects", "true".into(), "false".into());
+        }
+        if let (Some(max), Some(asked)) = (cluster.memory_mb, self.constraints.memory_mb) {
+            if asked > max {
+                return widened("memory_mb", asked.to_string(), max.to_string());
+            }
+        }
+
+        if self.air_gap {
+            if !cluster.air_gapped {
+                return Err(format!(
+                    "air_gap=true but {} is not air_gapped ({})",
+                    table, origin
+                ));
+            }
+            if cluster.deterministic != Some(true) {
+                return Err(format!(
+                    "air_gap=true but {} is not deterministic ({})",
+                    table, origin
+                ));
+            }
+            if !self.proof.as_ref().is_some_and(|p| p.require) {
+                return Err("air_gap=true requires proof/require = true".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ClusterAxioms {
+    pub fn load(path: &Path) -> Result<ClusterAxioms, String> {
+        let text =
+            fs::read_to_string(path).map_err(|_| format!("cannot read {}", path.display()))?;
+        ClusterAxioms::parse(&text, path).map_err(|e| format!("{} ({})", e, path.display()))
+    }
+
+    pub fn parse(text: &str, path: &Path) -> Result<ClusterAxioms, String> {
+        let doc: toml::Table = text.parse().map_err(|e| format!("invalid TOML: {}", e))?;
+        let clusters = doc
+            .get("axiom")
+            .and_then(|axiom| axiom.get("clusters"))
+            .and_then(toml::Value::as_table)
+            .ok_or("[axiom.clusters] is missing")?;
+
+        let mut declared = BTreeMap::new();
+        for (name, spec) in clusters {
+            let table = format!("[axiom.clusters.{}]", name);
+            let spec = spec
+                .as_table()
+                .ok_or_else(|| format!("{} must be a table", table))?;
+            for key in spec.keys() {
+                if !CLUSTER_FIELDS.contains(&key.as_str()) {
+                    return Err(format!("{} {}: unknown field", table, key));
+                }
+            }
+            let flag = |key: &str| -> Result<Option<bool>, String> {
+                spec.get(key)
+                    .map(|v| {
+                        v.as_bool()
+                            .ok_or_else(|| format!("{} {}: must be boolean", table, key))
+                    })
+                    .transpose()
+            };
+            let names = |key: &str| -> Result<Vec<String>, String> {
+                match spec.get(key) {
+                    None => Ok(Vec::new()),
+                    Some(list) => list
+                        .as_array()
+                        .and_then(|items| {
+                            items
+                                .iter()
+                                .map(|item| item.as_str().map(str::to_string))
+                                .collect::<Option<Vec<_>>>()
+                        })
+                        .ok_or_else(|| format!("{} {}: must be an array of strings", table, key)),
+                }
+            };
+            let memory_mb = spec
+                .get("memory_mb")
+                .map(|v| {
+                    v.as_integer()
+                        .and_then(|n| u64::try_from(n).ok())
+                        .ok_or_else(|| {
+                            format!("{} memory_mb: must be a non-negative integer", table)
+                        })
+                })
+                .transpose()?;
+            declared.insert(
+                name.clone(),
+                Cluster {
+                    name: name.clone(),
+                    deterministic: flag("deterministic")?,
+                    side_effects: flag("side_effects")?,
+                    memory_mb,
+                    allowed_kernels: names("allowed_kernels")?,
+                    allowed_domains: names("allowed_domains")?,
+                    air_gapped: flag("air_gapped")?.unwrap_or(false),
+                },
+            );
+        }
+        Ok(ClusterAxioms {
+            path: path.to_path_buf(),
+            clusters: declared,
+        })
+    }
+}
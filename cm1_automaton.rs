@@ -0,0 +1,275 @@
+// cm1_automaton.rs
+// CM-1 Stream Automaton
+// Version: v1
+//
+// The CM-1 control-stream automaton, shared by cm1-verify and the registry
+// profiles (cm1_profile.rs). A `Grammar` parameterises it: the reference
+// grammar is the frozen cm1-verify v1.0 automaton, and a profile narrows it to
+// its dictionary, bounds scope depth and the number of body scopes, decides
+// where the separator may stand, and allows a single header/body frame.
+
+use std::fmt;
+
+pub const NUL: u8 = 0x00;
+pub const SOH: u8 = 0x01;
+pub const STX: u8 = 0x02;
+pub const ETX: u8 = 0x03;
+pub const EOT: u8 = 0x04;
+pub const SO: u8 = 0x0E;
+pub const SI: u8 = 0x0F;
+pub const DLE: u8 = 0x10;
+pub const RS: u8 = 0x1E;
+pub const SP: u8 = 0x20;
+
+/// The cm1-verify v1.0 symbol set.
+pub const REFERENCE_ALPHABET: [u8; 13] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x0E, 0x0F, 0x10, 0x1C, 0x1D, 0x1E, 0x1F, 0x20,
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grammar {
+    pub alphabet: Vec<u8>,
+    pub separator_in_scope: bool,
+    pub separator_outside_scope: bool,
+    pub max_depth: Option<usize>,
+    pub min_scopes: usize,
+    pub max_scopes: Option<usize>,
+    pub single_frame: bool,
+}
+
+impl Grammar {
+    /// The frozen cm1-verify v1.0 automaton.
+    pub fn reference() -> Grammar {
+        Grammar {
+            alphabet: REFERENCE_ALPHABET.to_vec(),
+            separator_in_scope: false,
+            separator_outside_scope: true,
+            max_depth: None,
+            min_scopes: 0,
+            max_scopes: None,
+            single_frame: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// A byte outside the grammar's alphabet.
+    Symbol { offset: usize, byte: u8 },
+    /// A legal symbol in the wrong place, or a stream that ends unfinished
+    /// (offset = stream length, no byte).
+    Grammar {
+        offset: usize,
+        byte: Option<u8>,
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Symbol { offset, byte } => write!(
+                f,
+                "symbol outside the alphabet at offset {} (0x{:02X})",
+                offset, byte
+            ),
+            Violation::Grammar {
+                offset,
+                byte,
+                reason,
+            } => {
+                write!(f, "{} at offset {}", reason, offset)?;
+                match byte {
+                    Some(byte) => write!(f, " (0x{:02X})", byte),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Init,
+    NullZone,
+    Header,
+    Body,
+    Scope,
+    Literal,
+    End,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ReturnState {
+    Header,
+    Body,
+}
+
+#[derive(Debug)]
+struct ScopeStack {
+    depth: usize,
+    return_state: Option<ReturnState>,
+}
+
+impl ScopeStack {
+    fn new() -> Self {
+        Self {
+            depth: 0,
+            return_state: None,
+        }
+    }
+
+    fn push(&mut self, return_state: ReturnState) {
+        if self.depth == 0 {
+            self.return_state = Some(return_state);
+        }
+        self.depth = self.depth.saturating_add(1);
+    }
+
+    fn pop(&mut self) -> Option<ReturnState> {
+        if self.depth == 0 {
+            return None;
+        }
+        self.depth -= 1;
+        if self.depth == 0 {
+            self.return_state.take()
+        } else {
+            None
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.depth == 0
+    }
+}
+
+/// Runs the automaton over `bytes`. The error is the first violation.
+pub fn run(bytes: &[u8], grammar: &Grammar) -> Result<(), Violation> {
+    let mut state = State::Init;
+    let mut scope_stack = ScopeStack::new();
+    let mut literal_mask = false;
+    let mut last_control: Option<u8> = None;
+    let mut bodies = 0usize;
+    let mut scopes = 0usize;
+
+    for (offset, byte) in bytes.iter().copied().enumerate() {
+        if !grammar.alphabet.contains(&byte) {
+            return Err(Violation::Symbol { offset, byte });
+        }
+        let illegal = |reason| {
+            Err(Violation::Grammar {
+                offset,
+                byte: Some(byte),
+                reason,
+            })
+        };
+
+        last_control = Some(byte);
+
+        state = match state {
+            State::Init => match byte {
+                NUL => State::NullZone,
+                SOH => State::Header,
+                SP => State::Init,
+                _ => return illegal("header expected"),
+            },
+            State::NullZone => match byte {
+                NUL => State::NullZone,
+                SOH => State::Header,
+                _ => return illegal("header expected"),
+            },
+            State::Header => match byte {
+                STX if grammar.single_frame && bodies > 0 => return illegal("second body"),
+                STX => {
+                    bodies += 1;
+                    State::Body
+                }
+                SO if grammar.single_frame => return illegal("scope in header"),
+                SO => {
+                    scope_stack.push(ReturnState::Header);
+                    State::Scope
+                }
+                EOT if grammar.single_frame && bodies == 0 => {
+                    return illegal("collapse before body")
+                }
+                EOT => State::End,
+                SP => State::Header,
+                _ => return illegal("illegal symbol in header"),
+            },
+            State::Body => match byte {
+                ETX => State::Header,
+                SO => {
+                    scopes += 1;
+                    if grammar.max_scopes.is_some_and(|max| scopes > max) {
+                        return illegal("too many scopes");
+                    }
+                    scope_stack.push(ReturnState::Body);
+                    State::Scope
+                }
+                DLE => {
+                    literal_mask = true;
+                    State::Literal
+                }
+                RS if !grammar.separator_outside_scope => {
+                    return illegal("separator outside scope")
+                }
+                0x1C..=0x20 => State::Body,
+                _ => return illegal("illegal symbol in body"),
+            },
+            State::Scope => match byte {
+                SO => {
+                    scope_stack.push(ReturnState::Body);
+                    State::Scope
+                }
+                SI => {
+                    if scope_stack.depth == 0 {
+                        return illegal("scope close without open");
+                    }
+                    match scope_stack.pop() {
+                        Some(ReturnState::Header) => State::Header,
+                        Some(ReturnState::Body) => State::Body,
+                        None => State::Scope,
+                    }
+                }
+                RS if grammar.separator_in_scope => State::Scope,
+                _ => return illegal("illegal symbol in scope"),
+            },
+            State::Literal => match byte {
+                DLE => {
+                    literal_mask = false;
+                    State::Body
+                }
+                _ => State::Literal,
+            },
+            State::End => match byte {
+                SP => State::End,
+                _ => return illegal("symbol after collapse"),
+            },
+        };
+
+        if grammar.max_depth.is_some_and(|max| scope_stack.depth > max) {
+            return illegal("scope nests too deep");
+        }
+    }
+
+    let unfinished = |reason| {
+        Err(Violation::Grammar {
+            offset: bytes.len(),
+            byte: None,
+            reason,
+        })
+    };
+    if state != State::End {
+        return unfinished("stream ends before collapse");
+    }
+    if !scope_stack.is_empty() || literal_mask {
+        return unfinished("scope or literal left open");
+    }
+    if scopes < grammar.min_scopes {
+        return unfinished("too few scopes before collapse");
+    }
+    if last_control != Some(EOT) {
+        return unfinished("stream does not end on the collapse");
+    }
+    Ok(())
+}
@@ -0,0 +1,144 @@
+// cm1_profile.rs
+// CM-1 Profile Grammars
+// Version: v1
+//
+// A CM-1 profile (registry/cm1/profiles.json) is a grammar over the CM-1
+// dictionary, executed by the cm1-verify automaton (cm1_automaton.rs): one
+// SOH STX header, a body of SO/RS/SI scopes, and ETX EOT as the single
+// collapse. Profiles narrow the reference automaton to their dictionary and
+// differ only in the scope rules: how deep scopes may nest, how many top-level
+// scopes the body holds, and whether separators may stand outside a scope.
+//
+//   "rules": {
+//     "start": ["SOH", "STX"],
+//     "scope": ["SO", "RS", "SI"],          // open, separator, close
+//     "max_depth": 1,                       // null: unbounded
+//     "scopes": {"min": 0, "max": null},    // top-level scopes
+//     "separator_outside_scope": false,
+//     "end": ["ETX", "EOT"]
+//   }
+//
+// "triplet" is accepted as the older spelling of "scope". Omitted keys take
+// the values shown. start, scope and end name the automaton's own frame; a
+// profile cannot rebind them.
+
+use std::fs;
+
+use serde_json::Value;
+
+use crate::cm1_automaton::{self, Grammar, EOT, ETX, RS, SI, SO, SOH, STX};
+use crate::registry::Registry;
+use crate::scxq2_pack::{self, Dict};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub grammar: Grammar,
+}
+
+fn symbols(rules: &Value, key: &str, dict: &Dict) -> Result<Vec<u8>, String> {
+    rules
+        .get(key)
+        .and_then(Value::as_array)
+        .ok_or_else(|| format!("rules.{} must be an array of symbols", key))?
+        .iter()
+        .map(|symbol| {
+            let name = symbol
+                .as_str()
+                .ok_or_else(|| format!("rules.{}: symbols are names", key))?;
+            dict.bytes
+                .get(name)
+                .copied()
+                .ok_or_else(|| format!("rules.{}: {} is not in {}", key, name, dict.id))
+        })
+        .collect()
+}
+
+/// An optional count: absent or null is unbounded.
+fn bound(value: Option<&Value>, key: &str) -> Result<Option<usize>, String> {
+    match value {
+        None | Some(Value::Null) => Ok(None),
+        Some(v) => v
+            .as_u64()
+            .map(|n| Some(n as usize))
+            .ok_or_else(|| format!("rules.{} must be a count or null", key)),
+    }
+}
+
+impl Profile {
+    pub fn from_rules(name: &str, rules: &Value, dict: &Dict) -> Result<Profile, String> {
+        let scope_key = if rules.get("scope").is_some() {
+            "scope"
+        } else {
+            "triplet"
+        };
+        if symbols(rules, scope_key, dict)? != [SO, RS, SI] {
+            return Err(format!("rules.{} must be SO, RS, SI", scope_key));
+        }
+        if symbols(rules, "start", dict)? != [SOH, STX] {
+            return Err("rules.start must be SOH, STX".to_string());
+        }
+        if symbols(rules, "end", dict)? != [ETX, EOT] {
+            return Err("rules.end must be ETX, EOT".to_string());
+        }
+
+        let max_depth = match rules.get("max_depth") {
+            None => Some(1),
+            value => bound(value, "max_depth")?,
+        };
+        let scopes = rules.get("scopes");
+        let min_scopes = bound(scopes.and_then(|s| s.get("min")), "scopes.min")?.unwrap_or(0);
+        let max_scopes = bound(scopes.and_then(|s| s.get("max")), "scopes.max")?;
+        if max_scopes.is_some_and(|max| max < min_scopes) {
+            return Err("rules.scopes.max is below rules.scopes.min".to_string());
+        }
+        let separator_outside_scope = match rules.get("separator_outside_scope") {
+            None => false,
+            Some(v) => v
+                .as_bool()
+                .ok_or_else(|| "rules.separator_outside_scope must be boolean".to_string())?,
+        };
+
+        let mut grammar = Grammar::reference();
+        grammar.alphabet = dict.bytes.values().copied().collect();
+        grammar.separator_in_scope = true;
+        grammar.separator_outside_scope = separator_outside_scope;
+        grammar.max_depth = max_depth;
+        grammar.min_scopes = min_scopes;
+        grammar.max_scopes = max_scopes;
+        grammar.single_frame = true;
+        Ok(Profile {
+            name: name.to_string(),
+            grammar,
+        })
+    }
+
+    /// Loads a profile from the registry, resolving symbols through the
+    /// dictionary the profile names.
+    pub fn resolve(registry: &Registry, name: &str) -> Result<Profile, String> {
+        let entry = registry
+            .profile(name)
+            .ok_or_else(|| format!("unknown cm1_profile {}", name))?;
+        let dict = match entry.get("dictionary").and_then(Value::as_str) {
+            Some(rel) => {
+                let path = registry.project_root().join(rel);
+                let bytes =
+                    fs::read(&path).map_err(|_| format!("cannot read {}", path.display()))?;
+                let doc: Value = serde_json::from_slice(&bytes)
+                    .map_err(|e| format!("invalid JSON in {} ({})", path.display(), e))?;
+                scxq2_pack::parse_dict(&doc).map_err(|e| format!("{} ({})", e, path.display()))?
+            }
+            None => scxq2_pack::frozen_dict(),
+        };
+        let rules = entry
+            .get("rules")
+            .ok_or_else(|| format!("cm1_profile {} has no rules", name))?;
+        Profile::from_rules(name, rules, &dict).map_err(|e| format!("cm1_profile {}: {}", name, e))
+    }
+
+    /// Runs the CM-1 automaton under this profile over `stream`. The error
+    /// names the first offending offset.
+    pub fn check(&self, stream: &[u8]) -> Result<(), String> {
+        cm1_automaton::run(stream, &self.grammar).map_err(|v| format!("{}: {}", self.name, v))
+    }
+}
@@ -0,0 +1,385 @@
+// conformance.rs
+// Collapse Geometry Conformance Runner
+// Version: v1
+//
+// Runs both conformance formats in the tree with one result model:
+//
+//   docs/collapse.geometry.conformance.json    "tests", cm1 as decimal bytes,
+//                                              expect ACCEPT / REJECT
+//   conformance/vectors/*.json                 "vectors", input as spaced hex,
+//                                              expect as an exit code (0 accepts)
+//   cluster.conformance.manifest.xjson         "@tests", file relative to the
+//                                              manifest, expect ACCEPT / ILLEGAL
+//
+// A vector with a stream is checked against the CM-1 profile it names, or else
+// the profile of its geometry; a vector with a kernel is checked against the
+// geometry–kernel binding. A vector with an expr instead of an input is an
+// @state condition: it is accepted if it typechecks against the vector's state
+// and holds there. A vector with a file is a <cluster-call> document, accepted
+// if it is schema-valid and within the nearest pi-gccp.axioms.toml; a missing
+// or unreadable TOML means the vector cannot be run. A vector that names none
+// of these cannot be run and fails the suite.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::cluster_call::{self, ClusterAxioms, ClusterCall};
+use crate::cm1_profile::Profile;
+use crate::expr::{self, State};
+use crate::geometry_binding::GeometryRegistry;
+use crate::registry::Registry;
+use crate::scxq2_pack;
+use crate::scxq7_object::{Scalar, Type};
+
+/// Prefix of a suite-level `@geometry` that pins a geometry registry version.
+pub const GEOMETRY_VERSION_PREFIX: &str = "collapse-geometry/";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Accept,
+    Reject,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Accept => write!(f, "ACCEPT"),
+            Verdict::Reject => write!(f, "REJECT"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Vector {
+    pub name: String,
+    pub geometry: Option<String>,
+    pub kernel: Option<String>,
+    /// CM-1 profile named directly; otherwise the geometry's.
+    pub profile: Option<String>,
+    pub stream: Option<Vec<u8>>,
+    pub expression: Option<Expression>,
+    /// <cluster-call> document, resolved against the suite's directory.
+    pub document: Option<PathBuf>,
+    pub expect: Verdict,
+}
+
+/// An @state condition and the typed state it is evaluated in.
+#[derive(Debug, Clone)]
+pub struct Expression {
+    pub source: String,
+    pub types: BTreeMap<String, Type>,
+    pub state: State,
+}
+
+#[derive(Debug, Clone)]
+pub struct Suite {
+    pub name: String,
+    /// Geometry registry version pinned by the suite, if any.
+    pub geometry_version: Option<String>,
+    pub vectors: Vec<Vector>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub name: String,
+    pub expect: Verdict,
+    pub actual: Verdict,
+    /// Why the vector was rejected.
+    pub reason: Option<String>,
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        self.expect == self.actual
+    }
+}
+
+fn text(entry: &Value, key: &str) -> Option<String> {
+    entry.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Decimal-array tests (`tests`, `cm1`, ACCEPT/REJECT).
+fn decimal_vectors(tests: &[Value]) -> Result<Vec<Vector>, String> {
+    tests
+        .iter()
+        .enumerate()
+        .map(|(i, test)| {
+            let name = text(test, "name").ok_or_else(|| format!("/tests/{}/name: missing", i))?;
+            let stream = match test.get("cm1") {
+                None => None,
+                Some(bytes) => Some(
+                    bytes
+                        .as_array()
+                        .and_then(|bytes| {
+                            bytes
+                                .iter()
+                                .map(|b| b.as_u64().filter(|b| *b <= 0xFF).map(|b| b as u8))
+                                .collect::<Option<Vec<u8>>>()
+                        })
+                        .ok_or_else(|| format!("/tests/{}/cm1: must be an array of bytes", i))?,
+                ),
+            };
+            let expect = match test.get("expect").and_then(Value::as_str) {
+                Some("ACCEPT") => Verdict::Accept,
+                Some("REJECT") => Verdict::Reject,
+                _ => return Err(format!("/tests/{}/expect: must be ACCEPT or REJECT", i)),
+            };
+            Ok(Vector {
+                name,
+                geometry: text(test, "geometry"),
+                kernel: text(test, "kernel"),
+                profile: text(test, "profile"),
+                stream,
+                expression: None,
+                document: None,
+                expect,
+            })
+        })
+        .collect()
+}
+
+/// `"state": {"<field>": {"type": "int", "value": 0}, ...}` next to an `expr`.
+fn expression(vector: &Value, source: String, at: &str) -> Result<Expression, String> {
+    let mut types = BTreeMap::new();
+    let mut state = State::new();
+    if let Some(fields) = vector.get("state") {
+        let fields = fields
+            .as_object()
+            .ok_or_else(|| format!("{}/state: must be an object", at))?;
+        for (name, field) in fields {
+            let ty = field
+                .get("type")
+                .and_then(Value::as_str)
+                .and_then(Type::parse)
+                .ok_or_else(|| format!("{}/state/{}/type: must be a scalar type", at, name))?;
+            let value = field
+                .get("value")
+                .and_then(|value| Scalar::from_json(value, ty))
+                .ok_or_else(|| format!("{}/state/{}/value: must be {}", at, name, ty))?;
+            types.insert(name.clone(), ty);
+            state.insert(name.clone(), value);
+        }
+    }
+    Ok(Expression {
+        source,
+        types,
+        state,
+    })
+}
+
+/// Hex vectors (`vectors`, `input` or `expr`, exit code); the suite names the
+/// kernel.
+fn hex_vectors(doc: &Value, vectors: &[Value]) -> Result<Vec<Vector>, String> {
+    let kernel = text(doc, "@kernel");
+    vectors
+        .iter()
+        .enumerate()
+        .map(|(i, vector)| {
+            let name =
+                text(vector, "name").ok_or_else(|| format!("/vectors/{}/name: missing", i))?;
+            let (stream, expression) = match (text(vector, "input"), text(vector, "expr")) {
+                (Some(input), None) => (
+                    Some(
+                        scxq2_pack::parse_hex(&input)
+                            .map_err(|e| format!("/vectors/{}/input: {}", i, e))?,
+                    ),
+                    None,
+                ),
+                (None, Some(source)) => (
+                    None,
+                    Some(expression(vector, source, &format!("/vectors/{}", i))?),
+                ),
+                (Some(_), Some(_)) => {
+                    return Err(format!("/vectors/{}: input and expr are exclusive", i))
+                }
+                (None, None) => return Err(format!("/vectors/{}/input: missing", i)),
+            };
+            let expect = match vector.get("expect").and_then(Value::as_i64) {
+                Some(0) => Verdict::Accept,
+                Some(_) => Verdict::Reject,
+                None => return Err(format!("/vectors/{}/expect: must be an exit code", i)),
+            };
+            Ok(Vector {
+                name,
+                geometry: text(vector, "geometry"),
+                kernel: text(vector, "kernel").or_else(|| kernel.clone()),
+                profile: text(vector, "profile"),
+                stream,
+                expression,
+                document: None,
+                expect,
+            })
+        })
+        .collect()
+}
+
+/// Cluster-call tests (`@tests`, `file`, ACCEPT/ILLEGAL).
+fn document_vectors(tests: &[Value], dir: &Path) -> Result<Vec<Vector>, String> {
+    tests
+        .iter()
+        .enumerate()
+        .map(|(i, test)| {
+            let name = text(test, "id").ok_or_else(|| format!("/@tests/{}/id: missing", i))?;
+            let file = text(test, "file").ok_or_else(|| format!("/@tests/{}/file: missing", i))?;
+            let expect = match test.get("expect").and_then(Value::as_str) {
+                Some("ACCEPT") => Verdict::Accept,
+                Some("ILLEGAL") => Verdict::Reject,
+                _ => return Err(format!("/@tests/{}/expect: must be ACCEPT or ILLEGAL", i)),
+            };
+            Ok(Vector {
+                name,
+                geometry: None,
+                kernel: None,
+                profile: None,
+                stream: None,
+                expression: None,
+                document: Some(dir.join(file)),
+                expect,
+            })
+        })
+        .collect()
+}
+
+impl Suite {
+    pub fn parse(bytes: &[u8], origin: &str) -> Result<Suite, String> {
+        let doc: Value = serde_json::from_slice(bytes)
+            .map_err(|_| format!("conformance suite is not valid JSON ({})", origin))?;
+        let vectors = if let Some(tests) = doc.get("tests").and_then(Value::as_array) {
+            decimal_vectors(tests)
+        } else if let Some(vectors) = doc.get("vectors").and_then(Value::as_array) {
+            hex_vectors(&doc, vectors)
+        } else if let Some(tests) = doc.get("@tests").and_then(Value::as_array) {
+            let dir = Path::new(origin).parent().unwrap_or(Path::new(""));
+            document_vectors(tests, dir)
+        } else {
+            Err("conformance suite has neither tests nor vectors".to_string())
+        }
+        .map_err(|e| format!("{} ({})", e, origin))?;
+
+        let geometry_version = match text(&doc, "@geometry") {
+            None => None,
+            Some(pin) => Some(
+                pin.strip_prefix(GEOMETRY_VERSION_PREFIX)
+                    .map(str::to_string)
+                    .ok_or_else(|| {
+                        format!("@geometry {} is not a registry version ({})", pin, origin)
+                    })?,
+            ),
+        };
+        Ok(Suite {
+            name: text(&doc, "@suite")
+                .or_else(|| text(&doc, "@conformance"))
+                .unwrap_or_else(|| origin.to_string()),
+            geometry_version,
+            vectors,
+        })
+    }
+
+    /// Runs every vector. An error means the suite could not be run, not that
+    /// a vector was rejected.
+    pub fn run(
+        &self,
+        registry: &Registry,
+        geometries: &GeometryRegistry,
+    ) -> Result<Vec<Outcome>, String> {
+        if let Some(version) = &self.geometry_version {
+            registry.geometry(Some(version))?;
+        }
+        self.vectors
+            .iter()
+            .map(|vector| {
+                let verdict = run_vector(vector, registry, geometries)
+                    .map_err(|e| format!("{}: {}", vector.name, e))?;
+                Ok(Outcome {
+                    name: vector.name.clone(),
+                    expect: vector.expect,
+                    actual: if verdict.is_ok() {
+                        Verdict::Accept
+                    } else {
+                        Verdict::Reject
+                    },
+                    reason: verdict.err(),
+                })
+            })
+            .collect()
+    }
+}
+
+/// The outer error means the vector cannot be run; the inner one is a
+/// rejection.
+fn run_vector(
+    vector: &Vector,
+    registry: &Registry,
+    geometries: &GeometryRegistry,
+) -> Result<Result<(), String>, String> {
+    if let Some(expression) = &vector.expression {
+        return Ok(run_expression(expression));
+    }
+    if let Some(document) = &vector.document {
+        return run_document(document);
+    }
+    let geometry = match (&vector.geometry, &vector.kernel) {
+        (Some(name), _) => Some(geometries.geometry(name)?),
+        (None, Some(kernel)) => match geometries.allowing(kernel)[..] {
+            [geometry] => Some(geometry),
+            [] => return Err(format!("no geometry allows kernel {}", kernel)),
+            _ => {
+                return Err(format!(
+                    "kernel {} is allowed by several geometries",
+                    kernel
+                ))
+            }
+        },
+        (None, None) => None,
+    };
+    let profile = match (&vector.profile, geometry) {
+        (Some(profile), Some(geometry)) if *profile != geometry.cm1_profile => {
+            return Err(format!(
+                "profile {} is not the profile of {} ({})",
+                profile, geometry.name, geometry.cm1_profile
+            ))
+        }
+        (Some(profile), _) => profile,
+        (None, Some(geometry)) => &geometry.cm1_profile,
+        (None, None) => return Err("vector names no geometry, kernel or profile".to_string()),
+    };
+    if vector.kernel.is_none() && vector.stream.is_none() {
+        return Err("vector has no stream and no kernel".to_string());
+    }
+
+    if let (Some(kernel), Some(geometry)) = (&vector.kernel, geometry) {
+        if let Err(reason) = geometries.bind(&geometry.name, kernel) {
+            return Ok(Err(reason));
+        }
+    }
+    if let Some(stream) = &vector.stream {
+        return Ok(Profile::resolve(registry, profile)?.check(stream));
+    }
+    Ok(Ok(()))
+}
+
+fn run_expression(expression: &Expression) -> Result<(), String> {
+    expr::check_condition(&expression.source, &expression.types)?;
+    if expr::holds(&expression.source, &expression.state)? {
+        Ok(())
+    } else {
+        Err(format!("{:?} does not hold", expression.source))
+    }
+}
+
+fn run_document(path: &Path) -> Result<Result<(), String>, String> {
+    let bytes = fs::read(path).map_err(|_| format!("cannot read {}", path.display()))?;
+    let toml = cluster_call::discover_axioms(path).ok_or_else(|| {
+        format!(
+            "{} not found for {}",
+            cluster_call::CLUSTER_AXIOMS_FILE,
+            path.display()
+        )
+    })?;
+    let axioms = ClusterAxioms::load(&toml)?;
+    Ok(ClusterCall::parse(&bytes).and_then(|call| call.check(&axioms)))
+}
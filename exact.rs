@@ -0,0 +1,163 @@
+// exact.rs
+// Exact Rationals
+// Version: v1
+//
+// Integer-only P/Q arithmetic for kernels that forbid approximation. Every
+// finite f64 is an exact dyadic rational, sums use the binary-splitting merge
+// P1·Q2 + P2·Q1 / Q1·Q2, and the only rounding is the final projection to f64
+// (round to nearest, ties to even).
+
+use std::fmt;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ratio {
+    p: BigInt,
+    q: BigUint,
+}
+
+fn gcd(mut a: BigUint, mut b: BigUint) -> BigUint {
+    while !b.is_zero() {
+        let r = &a % &b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// 2^e as an f64, for e in [-1074, 1023].
+fn pow2(e: i64) -> f64 {
+    if e >= -1022 {
+        f64::from_bits(((e + 1023) as u64) << 52)
+    } else {
+        f64::from_bits(1u64 << (e + 1074))
+    }
+}
+
+/// Correctly rounded projection of p/q to f64 (nearest, ties to even). The
+/// pair need not be reduced. Fails when the magnitude overflows f64.
+pub fn project(p: &BigInt, q: &BigUint) -> Result<f64, String> {
+    if p.is_zero() {
+        return Ok(0.0);
+    }
+    let a = p.magnitude();
+    let b = q;
+    // Choose e so the quotient a / (b·2^e) has exactly 53 bits, or fewer
+    // when the result is subnormal.
+    let mut e = a.bits() as i64 - b.bits() as i64 - 53;
+    let (quotient, remainder, divisor) = loop {
+        e = e.max(-1074);
+        let (n, d) = if e >= 0 {
+            (a.clone(), b << e as usize)
+        } else {
+            (a << (-e) as usize, b.clone())
+        };
+        let quotient = &n / &d;
+        let bits = quotient.bits();
+        if bits > 53 {
+            e += 1;
+        } else if bits < 53 && e > -1074 {
+            e -= 1;
+        } else {
+            let remainder = n - &quotient * &d;
+            break (quotient, remainder, d);
+        }
+    };
+    let mut mantissa = quotient.to_u64().expect("quotient holds at most 53 bits");
+    let twice = remainder << 1usize;
+    if twice > divisor || (twice == divisor && mantissa & 1 == 1) {
+        mantissa += 1;
+    }
+    if e + 53 > 1024 || (e + 53 == 1024 && mantissa >> 53 == 1) {
+        return Err("collapse overflows f64".to_string());
+    }
+    let magnitude = mantissa as f64 * pow2(e);
+    Ok(if p.sign() == Sign::Minus {
+        -magnitude
+    } else {
+        magnitude
+    })
+}
+
+/// p/q truncated toward zero to `digits` decimal places: the single division
+/// of a binary-splitting collapse.
+pub fn decimal(p: &BigInt, q: &BigUint, digits: usize) -> String {
+    let scaled = p.magnitude() * BigUint::from(10u8).pow(digits as u32) / q;
+    let text = format!("{:0>width$}", scaled.to_string(), width = digits + 1);
+    let (whole, fraction) = text.split_at(text.len() - digits);
+    let sign = if p.sign() == Sign::Minus && !scaled.is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    if digits == 0 {
+        format!("{}{}", sign, whole)
+    } else {
+        format!("{}{}.{}", sign, whole, fraction)
+    }
+}
+
+impl Ratio {
+    /// p/q in lowest terms. Fails on a zero denominator.
+    pub fn new(p: BigInt, q: BigInt) -> Result<Ratio, String> {
+        if q.is_zero() {
+            return Err("zero denominator".to_string());
+        }
+        let p = if q.is_negative() { -p } else { p };
+        let q = q.magnitude().clone();
+        let g = gcd(p.magnitude().clone(), q.clone());
+        if g.is_one() || g.is_zero() {
+            return Ok(Ratio { p, q });
+        }
+        Ok(Ratio {
+            p: p / BigInt::from(g.clone()),
+            q: q / g,
+        })
+    }
+
+    /// The exact value of a finite f64.
+    pub fn from_f64(x: f64) -> Result<Ratio, String> {
+        if !x.is_finite() {
+            return Err(format!("{} is not finite", x));
+        }
+        let bits = x.to_bits();
+        let exponent = ((bits >> 52) & 0x7FF) as i64;
+        let fraction = bits & ((1u64 << 52) - 1);
+        let (mantissa, e) = if exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1u64 << 52), exponent - 1075)
+        };
+        let mut p = BigInt::from(mantissa);
+        if bits >> 63 == 1 {
+            p = -p;
+        }
+        if e >= 0 {
+            Ratio::new(p << e as usize, BigInt::one())
+        } else {
+            Ratio::new(p, BigInt::one() << (-e) as usize)
+        }
+    }
+
+    /// The binary-splitting merge: P1/Q1 + P2/Q2 = (P1·Q2 + P2·Q1) / (Q1·Q2).
+    pub fn add(&self, other: &Ratio) -> Ratio {
+        let q1 = BigInt::from(self.q.clone());
+        let q2 = BigInt::from(other.q.clone());
+        Ratio::new(&self.p * &q2 + &other.p * &q1, q1 * q2)
+            .expect("product of nonzero denominators is nonzero")
+    }
+
+    /// Correctly rounded projection to f64 (nearest, ties to even). Fails when
+    /// the magnitude overflows f64.
+    pub fn to_f64(&self) -> Result<f64, String> {
+        project(&self.p, &self.q)
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.p, self.q)
+    }
+}
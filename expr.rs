@@ -0,0 +1,539 @@
+// expr.rs
+// SCXQ7 State Expressions
+// Version: v1
+//
+// The expression language of @state invariants and step validate clauses,
+// e.g. "counter >= 0" or "balance ≥ 0 and limit - balance <= 100". It is total
+// and side-effect free: no loops, calls, assignment or I/O.
+//
+//   expr    := or
+//   or      := and (("or" | "||" | "∨") and)*
+//   and     := not (("and" | "&&" | "∧") not)*
+//   not     := ("not" | "!" | "¬") not | compare
+//   compare := sum (cmp sum)?          cmp: < <= > >= == != ≤ ≥ ≠
+//   sum     := product (("+" | "-") product)*
+//   product := unary (("*" | "/" | "%") unary)*
+//   unary   := "-" unary | atom
+//   atom    := int | float | "string" | true | false | field | "(" expr ")"
+//
+// Fields are @state names. The typechecker types every expression against the
+// declared @state types: arithmetic is numeric (int with int stays int, a float
+// operand makes it float, % is int only), ordering compares numbers with
+// numbers or strings with strings, == and != compare like types, and the
+// connectives take bools. Evaluation never panics: int overflow and division
+// by zero are faults, reported as errors, and an and/or skips its right side
+// once the left decides it. Parentheses, not, unary minus and each chained
+// operator nest one level; more than MAX_DEPTH levels is a parse error, so no
+// expression can exhaust the stack of the parser, typechecker or evaluator.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::scxq7_object::{Scalar, Type};
+
+/// Field name → current value.
+pub type State = BTreeMap<String, Scalar>;
+
+pub const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Cmp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arith {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Logic {
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Field(String),
+    Literal(Scalar),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Arith(Arith, Box<Expr>, Box<Expr>),
+    Compare(Cmp, Box<Expr>, Box<Expr>),
+    Logic(Logic, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Int(i64),
+    Float(f64),
+    Text(String),
+    /// Operators and parentheses, spelled in ASCII.
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [(&str, &str); 22] = [
+    ("<=", "<="),
+    (">=", ">="),
+    ("==", "=="),
+    ("!=", "!="),
+    ("&&", "and"),
+    ("||", "or"),
+    ("≤", "<="),
+    ("≥", ">="),
+    ("≠", "!="),
+    ("∧", "and"),
+    ("∨", "or"),
+    ("¬", "not"),
+    ("!", "not"),
+    ("<", "<"),
+    (">", ">"),
+    ("+", "+"),
+    ("-", "-"),
+    ("*", "*"),
+    ("/", "/"),
+    ("%", "%"),
+    ("(", "("),
+    (")", ")"),
+];
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, String> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    'scan: while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        for (spelling, symbol) in SYMBOLS {
+            let width = spelling.chars().count();
+            if chars[i..].iter().take(width).copied().eq(spelling.chars()) {
+                tokens.push((start, Token::Symbol(symbol)));
+                i += width;
+                continue 'scan;
+            }
+        }
+        if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let token = if literal.contains('.') {
+                literal.parse().ok().map(Token::Float)
+            } else {
+                literal.parse().ok().map(Token::Int)
+            };
+            tokens.push((
+                start,
+                token.ok_or_else(|| format!("bad number {} at {}", literal, start))?,
+            ));
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len()
+                && (chars[i].is_alphanumeric() || chars[i] == '_' || chars[i] == '.')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            tokens.push((
+                start,
+                match word.as_str() {
+                    "and" => Token::Symbol("and"),
+                    "or" => Token::Symbol("or"),
+                    "not" => Token::Symbol("not"),
+                    _ => Token::Ident(word),
+                },
+            ));
+        } else if c == '"' {
+            i += 1;
+            let mut text = String::new();
+            while i < chars.len() && chars[i] != '"' {
+                text.push(chars[i]);
+                i += 1;
+            }
+            if i == chars.len() {
+                return Err(format!("unterminated string at {}", start));
+            }
+            i += 1;
+            tokens.push((start, Token::Text(text)));
+        } else {
+            return Err(format!("unexpected '{}' at {}", c, start));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    next: usize,
+    end: usize,
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(_, token)| token)
+    }
+
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.next)
+            .map(|(offset, _)| *offset)
+            .unwrap_or(self.end)
+    }
+
+    /// Consumes the next token if it is one of `symbols`.
+    fn symbol(&mut self, symbols: &[&'static str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Token::Symbol(s)) if symbols.contains(s) => {
+                let s = *s;
+                self.next += 1;
+                Some(s)
+            }
+            _ => None,
+        }
+    }
+
+    /// Enters one nesting level at the token just consumed.
+    fn nest(&mut self) -> Result<(), String> {
+        if self.depth == MAX_DEPTH {
+            let offset = self.tokens[self.next - 1].0;
+            return Err(format!("nesting deeper than {} at {}", MAX_DEPTH, offset));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut left = self.and()?;
+        while self.symbol(&["or"]).is_some() {
+            self.nest()?;
+            left = Expr::Logic(Logic::Or, Box::new(left), Box::new(self.and()?));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut left = self.not()?;
+        while self.symbol(&["and"]).is_some() {
+            self.nest()?;
+            left = Expr::Logic(Logic::And, Box::new(left), Box::new(self.not()?));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn not(&mut self) -> Result<Expr, String> {
+        if self.symbol(&["not"]).is_some() {
+            self.nest()?;
+            let inner = self.not()?;
+            self.depth -= 1;
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.compare()
+    }
+
+    fn compare(&mut self) -> Result<Expr, String> {
+        const CMP: [&str; 6] = ["<", "<=", ">", ">=", "==", "!="];
+        let left = self.sum()?;
+        let Some(symbol) = self.symbol(&CMP) else {
+            return Ok(left);
+        };
+        let cmp = match symbol {
+            "<" => Cmp::Lt,
+            "<=" => Cmp::Le,
+            ">" => Cmp::Gt,
+            ">=" => Cmp::Ge,
+            "==" => Cmp::Eq,
+            _ => Cmp::Ne,
+        };
+        let right = self.sum()?;
+        let offset = self.offset();
+        if self.symbol(&CMP).is_some() {
+            return Err(format!("comparisons do not chain at {}", offset));
+        }
+        Ok(Expr::Compare(cmp, Box::new(left), Box::new(right)))
+    }
+
+    fn sum(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut left = self.product()?;
+        while let Some(symbol) = self.symbol(&["+", "-"]) {
+            self.nest()?;
+            let op = if symbol == "+" {
+                Arith::Add
+            } else {
+                Arith::Sub
+            };
+            left = Expr::Arith(op, Box::new(left), Box::new(self.product()?));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn product(&mut self) -> Result<Expr, String> {
+        let depth = self.depth;
+        let mut left = self.unary()?;
+        while let Some(symbol) = self.symbol(&["*", "/", "%"]) {
+            self.nest()?;
+            let op = match symbol {
+                "*" => Arith::Mul,
+                "/" => Arith::Div,
+                _ => Arith::Rem,
+            };
+            left = Expr::Arith(op, Box::new(left), Box::new(self.unary()?));
+        }
+        self.depth = depth;
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.symbol(&["-"]).is_some() {
+            self.nest()?;
+            let inner = self.unary()?;
+            self.depth -= 1;
+            return Ok(Expr::Neg(Box::new(inner)));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        let offset = self.offset();
+        let Some((_, token)) = self.tokens.get(self.next).cloned() else {
+            return Err(format!("expected an operand at {}", offset));
+        };
+        self.next += 1;
+        match token {
+            Token::Ident(name) => Ok(match name.as_str() {
+                "true" => Expr::Literal(Scalar::Bool(true)),
+                "false" => Expr::Literal(Scalar::Bool(false)),
+                _ => Expr::Field(name),
+            }),
+            Token::Int(n) => Ok(Expr::Literal(Scalar::Int(n))),
+            Token::Float(x) => Ok(Expr::Literal(Scalar::Float(x))),
+            Token::Text(s) => Ok(Expr::Literal(Scalar::Text(s))),
+            Token::Symbol("(") => {
+                self.nest()?;
+                let inner = self.or()?;
+                if self.symbol(&[")"]).is_none() {
+                    return Err(format!("expected ) at {}", self.offset()));
+                }
+                self.depth -= 1;
+                Ok(inner)
+            }
+            Token::Symbol(symbol) => Err(format!(
+                "expected an operand, found {} at {}",
+                symbol, offset
+            )),
+        }
+    }
+}
+
+/// Parses a whole expression; offsets in errors count characters.
+pub fn parse(source: &str) -> Result<Expr, String> {
+    let parsed = tokenize(source).and_then(|tokens| {
+        let mut parser = Parser {
+            tokens,
+            next: 0,
+            end: source.chars().count(),
+            depth: 0,
+        };
+        let expr = parser.or()?;
+        if parser.peek().is_some() {
+            return Err(format!("unexpected input at {}", parser.offset()));
+        }
+        Ok(expr)
+    });
+    parsed.map_err(|e| format!("{:?}: {}", source, e))
+}
+
+fn literal_type(value: &Scalar) -> Type {
+    match value {
+        Scalar::Int(_) => Type::Int,
+        Scalar::Float(_) => Type::Float,
+        Scalar::Bool(_) => Type::Bool,
+        Scalar::Text(_) => Type::String,
+    }
+}
+
+/// Types `expr` against the declared field types.
+pub fn typecheck(expr: &Expr, fields: &BTreeMap<String, Type>) -> Result<Type, String> {
+    match expr {
+        Expr::Field(name) => fields
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("{} is not a @state field", name)),
+        Expr::Literal(value) => Ok(literal_type(value)),
+        Expr::Neg(inner) => match typecheck(inner, fields)? {
+            ty if ty.is_numeric() => Ok(ty),
+            ty => Err(format!("cannot negate {}", ty)),
+        },
+        Expr::Not(inner) => match typecheck(inner, fields)? {
+            Type::Bool => Ok(Type::Bool),
+            ty => Err(format!("not takes bool, found {}", ty)),
+        },
+        Expr::Arith(op, left, right) => {
+            match (op, typecheck(left, fields)?, typecheck(right, fields)?) {
+                (Arith::Rem, Type::Int, Type::Int) => Ok(Type::Int),
+                (Arith::Rem, l, r) => Err(format!("% takes int, found {} and {}", l, r)),
+                (_, Type::Int, Type::Int) => Ok(Type::Int),
+                (_, l, r) if l.is_numeric() && r.is_numeric() => Ok(Type::Float),
+                (_, l, r) => Err(format!("arithmetic on {} and {}", l, r)),
+            }
+        }
+        Expr::Compare(cmp, left, right) => {
+            let (l, r) = (typecheck(left, fields)?, typecheck(right, fields)?);
+            let comparable = if l.is_numeric() && r.is_numeric() {
+                true
+            } else if matches!(cmp, Cmp::Eq | Cmp::Ne) {
+                l == r
+            } else {
+                l == Type::String && r == Type::String
+            };
+            if comparable {
+                Ok(Type::Bool)
+            } else {
+                Err(format!("cannot compare {} with {}", l, r))
+            }
+        }
+        Expr::Logic(_, left, right) => {
+            match (typecheck(left, fields)?, typecheck(right, fields)?) {
+                (Type::Bool, Type::Bool) => Ok(Type::Bool),
+                (l, r) => Err(format!("and/or take bool, found {} and {}", l, r)),
+            }
+        }
+    }
+}
+
+/// Parses `source` and checks it is a bool over the declared fields.
+pub fn check_condition(source: &str, fields: &BTreeMap<String, Type>) -> Result<Expr, String> {
+    let expr = parse(source)?;
+    match typecheck(&expr, fields).map_err(|e| format!("{:?}: {}", source, e))? {
+        Type::Bool => Ok(expr),
+        ty => Err(format!("{:?}: must be bool, found {}", source, ty)),
+    }
+}
+
+fn float(value: &Scalar) -> Option<f64> {
+    match value {
+        Scalar::Int(n) => Some(*n as f64),
+        Scalar::Float(x) => Some(*x),
+        _ => None,
+    }
+}
+
+fn arith(op: Arith, left: &Scalar, right: &Scalar) -> Result<Scalar, String> {
+    if let (Scalar::Int(a), Scalar::Int(b)) = (left, right) {
+        let (a, b) = (*a, *b);
+        if matches!(op, Arith::Div | Arith::Rem) && b == 0 {
+            return Err("division by zero".to_string());
+        }
+        return match op {
+            Arith::Add => a.checked_add(b),
+            Arith::Sub => a.checked_sub(b),
+            Arith::Mul => a.checked_mul(b),
+            Arith::Div => a.checked_div(b),
+            Arith::Rem => a.checked_rem(b),
+        }
+        .map(Scalar::Int)
+        .ok_or_else(|| "int overflow".to_string());
+    }
+    let (Some(a), Some(b)) = (float(left), float(right)) else {
+        return Err("arithmetic on non-numbers".to_string());
+    };
+    let x = match op {
+        Arith::Add => a + b,
+        Arith::Sub => a - b,
+        Arith::Mul => a * b,
+        Arith::Div if b == 0.0 => return Err("division by zero".to_string()),
+        Arith::Div => a / b,
+        Arith::Rem => return Err("% on float".to_string()),
+    };
+    if x.is_finite() {
+        Ok(Scalar::Float(x))
+    } else {
+        Err("float result is not finite".to_string())
+    }
+}
+
+fn order(left: &Scalar, right: &Scalar) -> Result<Option<Ordering>, String> {
+    match (left, right) {
+        (Scalar::Int(a), Scalar::Int(b)) => Ok(Some(a.cmp(b))),
+        (Scalar::Bool(a), Scalar::Bool(b)) => Ok(Some(a.cmp(b))),
+        (Scalar::Text(a), Scalar::Text(b)) => Ok(Some(a.cmp(b))),
+        _ => match (float(left), float(right)) {
+            (Some(a), Some(b)) => Ok(a.partial_cmp(&b)),
+            _ => Err("operands have different types".to_string()),
+        },
+    }
+}
+
+fn boolean(value: Scalar) -> Result<bool, String> {
+    match value {
+        Scalar::Bool(b) => Ok(b),
+        _ => Err("not a bool".to_string()),
+    }
+}
+
+pub fn eval(expr: &Expr, state: &State) -> Result<Scalar, String> {
+    match expr {
+        Expr::Field(name) => state
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("unknown field {}", name)),
+        Expr::Literal(value) => Ok(value.clone()),
+        Expr::Neg(inner) => match eval(inner, state)? {
+            Scalar::Int(n) => n
+                .checked_neg()
+                .map(Scalar::Int)
+                .ok_or_else(|| "int overflow".to_string()),
+            Scalar::Float(x) => Ok(Scalar::Float(-x)),
+            _ => Err("cannot negate a non-number".to_string()),
+        },
+        Expr::Not(inner) => Ok(Scalar::Bool(!boolean(eval(inner, state)?)?)),
+        Expr::Arith(op, left, right) => arith(*op, &eval(left, state)?, &eval(right, state)?),
+        Expr::Compare(cmp, left, right) => {
+            let ordering = order(&eval(left, state)?, &eval(right, state)?)?;
+            Ok(Scalar::Bool(match ordering {
+                None => *cmp == Cmp::Ne,
+                Some(o) => match cmp {
+                    Cmp::Lt => o == Ordering::Less,
+                    Cmp::Le => o != Ordering::Greater,
+                    Cmp::Gt => o == Ordering::Greater,
+                    Cmp::Ge => o != Ordering::Less,
+                    Cmp::Eq => o == Ordering::Equal,
+                    Cmp::Ne => o != Ordering::Equal,
+                },
+            }))
+        }
+        Expr::Logic(logic, left, right) => {
+            let left = boolean(eval(left, state)?)?;
+            Ok(Scalar::Bool(match (logic, left) {
+                (Logic::And, false) => false,
+                (Logic::Or, true) => true,
+                _ => boolean(eval(right, state)?)?,
+            }))
+        }
+    }
+}
+
+/// Evaluates `source` as a condition: it must hold and be boolean.
+pub fn holds(source: &str, state: &State) -> Result<bool, String> {
+    boolean(eval(&parse(source)?, state).map_err(|e| format!("{:?}: {}", source, e))?)
+        .map_err(|_| format!("{:?}: not a condition", source))
+}
@@ -0,0 +1,224 @@
+// geometry_binding.rs
+// Geometry–Kernel–Profile Binding Check
+// Version: v1
+//
+// docs/collapse.geometry.registry.xjson binds each collapse geometry to the
+// kernels it allows and to one CM-1 profile. A kernel file binds itself to a
+// geometry and a profile; the three sources must agree. Every disagreement is
+// reported as a contradiction, never resolved by preference.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::kernel_manifest::GeometryAxioms;
+use crate::registry::{Issue, Registry};
+
+pub const GEOMETRY_REGISTRY_FILE: &str = "docs/collapse.geometry.registry.xjson";
+
+#[derive(Debug, Clone)]
+pub struct Geometry {
+    pub name: String,
+    pub allowed_kernels: Vec<String>,
+    pub cm1_profile: String,
+    pub associative: bool,
+    pub locality: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeometryRegistry {
+    pub path: PathBuf,
+    pub geometries: BTreeMap<String, Geometry>,
+}
+
+impl GeometryRegistry {
+    pub fn load(path: &Path) -> Result<GeometryRegistry, String> {
+        let bytes = fs::read(path).map_err(|_| format!("cannot read {}", path.display()))?;
+        let doc: Value = serde_json::from_slice(&bytes)
+            .map_err(|e| format!("invalid JSON in {} ({})", path.display(), e))?;
+        let entries = doc
+            .get("geometries")
+            .and_then(Value::as_object)
+            .ok_or_else(|| format!("missing geometries ({})", path.display()))?;
+
+        let mut geometries = BTreeMap::new();
+        for (name, entry) in entries {
+            let field = |key: &str| {
+                entry.get(key).ok_or_else(|| {
+                    format!("geometry {} missing {} ({})", name, key, path.display())
+                })
+            };
+            let allowed_kernels = field("allowed_kernels")?
+                .as_array()
+                .and_then(|kernels| {
+                    kernels
+                        .iter()
+                        .map(|k| k.as_str().map(str::to_string))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| {
+                    format!(
+                        "geometry {}.allowed_kernels must be strings ({})",
+                        name,
+                        path.display()
+                    )
+                })?;
+            let text = |key: &str| {
+                field(key)?.as_str().map(str::to_string).ok_or_else(|| {
+                    format!(
+                        "geometry {}.{} must be a string ({})",
+                        name,
+                        key,
+                        path.display()
+                    )
+                })
+            };
+            let associative = field("associative")?.as_bool().ok_or_else(|| {
+                format!(
+                    "geometry {}.associative must be boolean ({})",
+                    name,
+                    path.display()
+                )
+            })?;
+            geometries.insert(
+                name.clone(),
+                Geometry {
+                    name: name.clone(),
+                    allowed_kernels,
+                    cm1_profile: text("cm1_profile")?,
+                    associative,
+                    locality: text("locality")?,
+                },
+            );
+        }
+        Ok(GeometryRegistry {
+            path: path.to_path_buf(),
+            geometries,
+        })
+    }
+
+    pub fn geometry(&self, name: &str) -> Result<&Geometry, String> {
+        self.geometries
+            .get(name)
+            .ok_or_else(|| format!("unknown collapse geometry {}", name))
+    }
+
+    /// Checks that `geometry` allows `kernel` and returns the geometry.
+    pub fn bind(&self, geometry: &str, kernel: &str) -> Result<&Geometry, String> {
+        let entry = self.geometry(geometry)?;
+        if !entry.allowed_kernels.iter().any(|k| k == kernel) {
+            return Err(format!(
+                "kernel {} is not allowed on {} (allowed: {})",
+                kernel,
+                geometry,
+                entry.allowed_kernels.join(", ")
+            ));
+        }
+        Ok(entry)
+    }
+
+    /// Geometries whose allowed_kernels name `kernel`.
+    pub fn allowing(&self, kernel: &str) -> Vec<&Geometry> {
+        self.geometries
+            .values()
+            .filter(|g| g.allowed_kernels.iter().any(|k| k == kernel))
+            .collect()
+    }
+}
+
+/// Every contradiction between the kernel files and CM-1 profiles of
+/// `registry`, the geometry registry and (when given) the geometry axioms.
+pub fn contradictions(
+    registry: &Registry,
+    geometries: &GeometryRegistry,
+    axioms: Option<&GeometryAxioms>,
+) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let mut report = |path: &Path, message: String| {
+        issues.push(Issue {
+            path: path.to_path_buf(),
+            message,
+        })
+    };
+
+    for geometry in geometries.geometries.values() {
+        if registry.profile(&geometry.cm1_profile).is_none() {
+            report(
+                &geometries.path,
+                format!(
+                    "geometry {} names unknown cm1_profile {}",
+                    geometry.name, geometry.cm1_profile
+                ),
+            );
+        }
+        if let Some(axioms) = axioms {
+            match axioms.properties.get(&geometry.name) {
+                None => report(
+                    &geometries.path,
+                    format!("geometry {} is not in the geometry axioms", geometry.name),
+                ),
+                Some(props) => {
+                    if props.associative != geometry.associative {
+                        report(
+                            &geometries.path,
+                            format!(
+                                "geometry {} associative is {}, axioms say {}",
+                                geometry.name, geometry.associative, props.associative
+                            ),
+                        );
+                    }
+                    if props.locality != geometry.locality {
+                        report(
+                            &geometries.path,
+                            format!(
+                                "geometry {} locality is {}, axioms say {}",
+                                geometry.name, geometry.locality, props.locality
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    for kernel in registry.kernels.values() {
+        let bound: Vec<&Geometry> = match kernel.collapse_geometry.as_deref() {
+            Some(name) => match geometries.bind(name, &kernel.id) {
+                Ok(geometry) => vec![geometry],
+                Err(reason) => {
+                    report(&kernel.path, reason);
+                    continue;
+                }
+            },
+            None => {
+                let allowing = geometries.allowing(&kernel.id);
+                if allowing.is_empty() {
+                    report(
+                        &kernel.path,
+                        format!("kernel {} is allowed by no geometry", kernel.id),
+                    );
+                    continue;
+                }
+                allowing
+            }
+        };
+        let Some(profile) = kernel.cm1_profile.as_deref() else {
+            continue;
+        };
+        for geometry in bound {
+            if geometry.cm1_profile != profile {
+                report(
+                    &kernel.path,
+                    format!(
+                        "kernel {} declares cm1_profile {}, geometry {} requires {}",
+                        kernel.id, profile, geometry.name, geometry.cm1_profile
+                    ),
+                );
+            }
+        }
+    }
+
+    issues
+}
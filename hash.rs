@@ -0,0 +1,144 @@
+// hash.rs
+// Algorithm-Tagged Digests
+// Version: v1
+//
+// Every digest carries its algorithm: `sha256:<hex>`, `blake3:<hex>`,
+// `SHA3-256:<hex>`. Checks name the algorithm the schema requires, so a digest
+// produced by a different algorithm is refused rather than compared.
+
+use std::fmt;
+
+use sha2::Digest as _;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    Sha256,
+    Blake3,
+    Sha3_256,
+}
+
+impl Algorithm {
+    pub fn tag(self) -> &'static str {
+        match self {
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Blake3 => "blake3",
+            Algorithm::Sha3_256 => "SHA3-256",
+        }
+    }
+
+    pub fn from_tag(tag: &str) -> Option<Algorithm> {
+        match tag {
+            "sha256" => Some(Algorithm::Sha256),
+            "blake3" => Some(Algorithm::Blake3),
+            "SHA3-256" => Some(Algorithm::Sha3_256),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Digest {
+    pub algorithm: Algorithm,
+    pub bytes: [u8; 32],
+}
+
+pub enum Hasher {
+    Sha256(sha2::Sha256),
+    Blake3(Box<blake3::Hasher>),
+    Sha3_256(Box<sha3::Sha3_256>),
+}
+
+impl Hasher {
+    pub fn new(algorithm: Algorithm) -> Hasher {
+        match algorithm {
+            Algorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            Algorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            Algorithm::Sha3_256 => Hasher::Sha3_256(Box::new(sha3::Sha3_256::new())),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(h) => h.update(data),
+            Hasher::Blake3(h) => {
+                h.update(data);
+            }
+            Hasher::Sha3_256(h) => h.update(data),
+        }
+    }
+
+    pub fn finalize(self) -> Digest {
+        match self {
+            Hasher::Sha256(h) => Digest {
+                algorithm: Algorithm::Sha256,
+                bytes: h.finalize().into(),
+            },
+            Hasher::Blake3(h) => Digest {
+                algorithm: Algorithm::Blake3,
+                bytes: *h.finalize().as_bytes(),
+            },
+            Hasher::Sha3_256(h) => Digest {
+                algorithm: Algorithm::Sha3_256,
+                bytes: h.finalize().into(),
+            },
+        }
+    }
+}
+
+impl Digest {
+    pub fn of(algorithm: Algorithm, data: &[u8]) -> Digest {
+        let mut hasher = Hasher::new(algorithm);
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    pub fn hex(&self) -> String {
+        self.bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Parses `tag:hex`. Untagged hex is rejected.
+    pub fn parse(text: &str) -> Result<Digest, String> {
+        let (tag, hex) = text
+            .split_once(':')
+            .ok_or_else(|| "digest has no algorithm tag".to_string())?;
+        let algorithm =
+            Algorithm::from_tag(tag).ok_or_else(|| format!("unknown digest algorithm {}", tag))?;
+        if hex.len() != 64 || !hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+            return Err(format!("{} digest must be 64 lowercase hex digits", tag));
+        }
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+                .map_err(|_| format!("{} digest must be 64 lowercase hex digits", tag))?;
+        }
+        Ok(Digest { algorithm, bytes })
+    }
+
+    /// Refuses a digest whose algorithm is not the one required.
+    pub fn expect(&self, required: Algorithm) -> Result<(), String> {
+        if self.algorithm != required {
+            return Err(format!(
+                "digest algorithm {} where {} is required",
+                self.algorithm.tag(),
+                required.tag()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parses `text`, requires `algorithm`, and checks it against `data`.
+    pub fn check(text: &str, required: Algorithm, data: &[u8]) -> Result<(), String> {
+        let digest = Digest::parse(text)?;
+        digest.expect(required)?;
+        if digest != Digest::of(required, data) {
+            return Err("digest mismatch".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.algorithm.tag(), self.hex())
+    }
+}
@@ -0,0 +1,452 @@
+// idb.rs
+// IDB.xml Ledger (x:scxq7:idb)
+// Version: v1
+//
+// Reader and canonical writer for IDB.xml, the read-only ledger of
+// docs/idb-stack-frozen-core-v1.md. The reader enforces idb.schema.xsd: the
+// x:scxq7:idb namespace, element order state → causal → constraints, step
+// children cause → effect → proof → constraints, required attributes and no
+// unknown elements, attributes or text. Continuity is checked separately: hashes
+// are algorithm-tagged, logical times increase, constraint refs resolve, the
+// last step's effect is the head state, and an `scxq2` heap is a pack that
+// verifies and whose LANE hashes to it.
+//
+// The writer projects an executor history: one <step> per committed causal
+// step (effect = state hash after the step), the head state as an `scxq2` heap,
+// and the object's @state invariants as one constraint set. The heap is an
+// scxq2.pack.v1 pack whose LANE holds the canonical state bytes and whose FIELD
+// binds the object's collapse mode, its id and the executor. Output is
+// byte-identical for the same history: fixed element and attribute order,
+// two-space indent, `\n` line ends, escaped text.
+//
+// Replay re-executes a ledger against the object that produced it: each
+// step's event is the object cause whose hash is the recorded cause, and the
+// replayed logical time, state hash (the recorded effect) and proof must equal
+// the record, step by step, ending at the head state.
+
+use std::iter::Peekable;
+use std::vec::IntoIter;
+
+use roxmltree::{Document, Node};
+
+use crate::hash::Digest;
+use crate::scxq2_pack::{Field, Pack};
+use crate::scxq7_cse::{self, Executor};
+use crate::scxq7_object::Object;
+
+pub const IDB_FILE: &str = "IDB.xml";
+pub const IDB_NAMESPACE: &str = "x:scxq7:idb";
+pub const IDB_VERSION: &str = "1.0";
+pub const HEAP_ENCODING: &str = "scxq2";
+pub const STEP_ID_PREFIX: &str = "S_";
+pub const TIME_PREFIX: &str = "L_";
+pub const CONSTRAINTS_ID_PREFIX: &str = "C_";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub id: String,
+    pub time: String,
+    pub cause: String,
+    pub effect: String,
+    pub proof: String,
+    pub constraints: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraints {
+    pub id: String,
+    pub invariants: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Idb {
+    pub version: String,
+    pub state: String,
+    pub heap_encoding: String,
+    pub heap: String,
+    pub steps: Vec<Step>,
+    pub constraints: Option<Constraints>,
+}
+
+type Elements<'a, 'i> = Peekable<IntoIter<Node<'a, 'i>>>;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// `L_<n>` → n.
+pub fn logical_time(time: &str) -> Option<u64> {
+    time.strip_prefix(TIME_PREFIX)
+        .filter(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|n| n.parse().ok())
+}
+
+/// The head state of `executor` as an SCXQ2 pack over its canonical bytes.
+fn heap(executor: &Executor) -> Pack {
+    let object = executor.object();
+    let field = Field {
+        collapse_geometry: object.collapse_mode.clone(),
+        kernel: object.object_id.clone(),
+        cm1_profile: scxq7_cse::ENGINE.to_string(),
+    };
+    Pack::new(field, scxq7_cse::encode_state(executor.state()))
+}
+
+/// Element children, rejecting stray text; comments are ignored.
+fn children<'a, 'i>(node: Node<'a, 'i>, path: &str) -> Result<Vec<Node<'a, 'i>>, String> {
+    let mut elements = Vec::new();
+    for child in node.children() {
+        if child.is_element() {
+            if child.tag_name().namespace() != Some(IDB_NAMESPACE) {
+                return Err(format!(
+                    "{}/{}: not in the {} namespace",
+                    path,
+                    child.tag_name().name(),
+                    IDB_NAMESPACE
+                ));
+            }
+            elements.push(child);
+        } else if child.is_text() && !child.text().unwrap_or("").trim().is_empty() {
+            return Err(format!("{}: text is not allowed here", path));
+        }
+    }
+    Ok(elements)
+}
+
+/// Checks the attribute set and returns the required values in order.
+fn attributes(node: Node, path: &str, names: &[&str]) -> Result<Vec<String>, String> {
+    for attribute in node.attributes() {
+        if attribute.namespace().is_some() || !names.contains(&attribute.name()) {
+            return Err(format!("{}/@{}: unknown attribute", path, attribute.name()));
+        }
+    }
+    names
+        .iter()
+        .map(|name| {
+            node.attribute(*name)
+                .map(str::to_string)
+                .ok_or_else(|| format!("{}/@{}: missing", path, name))
+        })
+        .collect()
+}
+
+/// Takes the next child if it is named `name`.
+fn next<'a, 'i>(elements: &mut Elements<'a, 'i>, name: &str) -> Option<Node<'a, 'i>> {
+    elements.next_if(|node| node.tag_name().name() == name)
+}
+
+fn ref_hash(elements: &mut Elements, path: &str, name: &str) -> Result<String, String> {
+    let node = next(elements, name).ok_or_else(|| format!("{}/{}: missing", path, name))?;
+    let here = format!("{}/{}", path, name);
+    no_content(node, &here)?;
+    Ok(attributes(node, &here, &["hash"])?.remove(0))
+}
+
+fn no_content(node: Node, path: &str) -> Result<(), String> {
+    if children(node, path)?.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{}: must be empty", path))
+    }
+}
+
+fn unexpected(node: Option<Node>, path: &str) -> Result<(), String> {
+    match node {
+        None => Ok(()),
+        Some(node) => Err(format!(
+            "{}/{}: unexpected element",
+            path,
+            node.tag_name().name()
+        )),
+    }
+}
+
+fn parse_constraints(node: Node, path: &str) -> Result<Constraints, String> {
+    let id = attributes(node, path, &["id"])?.remove(0);
+    let invariants = children(node, path)?
+        .into_iter()
+        .map(|child| {
+            let here = format!("{}/{}", path, child.tag_name().name());
+            if child.tag_name().name() != "invariant" {
+                return Err(format!("{}: unexpected element", here));
+            }
+            attributes(child, &here, &[])?;
+            if child.children().any(|c| c.is_element()) {
+                return Err(format!("{}: must be text", here));
+            }
+            Ok(child.text().unwrap_or("").to_string())
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if invariants.is_empty() {
+        return Err(format!("{}: needs at least one invariant", path));
+    }
+    Ok(Constraints { id, invariants })
+}
+
+impl Idb {
+    /// Reads IDB.xml, enforcing idb.schema.xsd.
+    pub fn parse(bytes: &[u8]) -> Result<Idb, String> {
+        let text = std::str::from_utf8(bytes).map_err(|_| "IDB.xml is not UTF-8".to_string())?;
+        let doc = Document::parse(text).map_err(|e| format!("invalid XML ({})", e))?;
+        let root = doc.root_element();
+        if root.tag_name().name() != "idb" || root.tag_name().namespace() != Some(IDB_NAMESPACE) {
+            return Err(format!(
+                "root must be idb in the {} namespace",
+                IDB_NAMESPACE
+            ));
+        }
+        let version = attributes(root, "/idb", &["version"])?.remove(0);
+        let mut elements = children(root, "/idb")?.into_iter().peekable();
+
+        let state = next(&mut elements, "state").ok_or("/idb/state: missing")?;
+        let state_hash = attributes(state, "/idb/state", &["hash"])?.remove(0);
+        let mut state_children = children(state, "/idb/state")?.into_iter().peekable();
+        let heap = next(&mut state_children, "heap").ok_or("/idb/state/heap: missing")?;
+        unexpected(state_children.next(), "/idb/state")?;
+        let heap_encoding = attributes(heap, "/idb/state/heap", &["encoding"])?.remove(0);
+        if heap.children().any(|c| c.is_element()) {
+            return Err("/idb/state/heap: must be text".to_string());
+        }
+
+        let causal = next(&mut elements, "causal").ok_or("/idb/causal: missing")?;
+        attributes(causal, "/idb/causal", &[])?;
+        let mut steps = Vec::new();
+        for (i, step) in children(causal, "/idb/causal")?.into_iter().enumerate() {
+            let path = format!("/idb/causal/step[{}]", i + 1);
+            if step.tag_name().name() != "step" {
+                return Err(format!("{}: unexpected element", path));
+            }
+            let attrs = attributes(step, &path, &["id", "time"])?;
+            let mut refs = children(step, &path)?.into_iter().peekable();
+            let cause = ref_hash(&mut refs, &path, "cause")?;
+            let effect = ref_hash(&mut refs, &path, "effect")?;
+            let proof = ref_hash(&mut refs, &path, "proof")?;
+            let constraints = match next(&mut refs, "constraints") {
+                None => None,
+                Some(node) => {
+                    let here = format!("{}/constraints", path);
+                    no_content(node, &here)?;
+                    Some(attributes(node, &here, &["ref"])?.remove(0))
+                }
+            };
+            unexpected(refs.next(), &path)?;
+            steps.push(Step {
+                id: attrs[0].clone(),
+                time: attrs[1].clone(),
+                cause,
+                effect,
+                proof,
+                constraints,
+            });
+        }
+        if steps.is_empty() {
+            return Err("/idb/causal: needs at least one step".to_string());
+        }
+
+        let constraints = match next(&mut elements, "constraints") {
+            None => None,
+            Some(node) => Some(parse_constraints(node, "/idb/constraints")?),
+        };
+        unexpected(elements.next(), "/idb")?;
+
+        Ok(Idb {
+            version,
+            state: state_hash,
+            heap_encoding,
+            heap: heap.text().unwrap_or("").to_string(),
+            steps,
+            constraints,
+        })
+    }
+
+    /// Hash continuity across the ledger.
+    pub fn check_continuity(&self) -> Result<(), String> {
+        let state = Digest::parse(&self.state).map_err(|e| format!("state hash: {}", e))?;
+        let mut last_time = None;
+        for (i, step) in self.steps.iter().enumerate() {
+            let path = format!("step {}", step.id);
+            if self.steps[..i].iter().any(|s| s.id == step.id) {
+                return Err(format!("{}: duplicate step id", path));
+            }
+            let time = logical_time(&step.time)
+                .ok_or_else(|| format!("{}: time {} is not {}<n>", path, step.time, TIME_PREFIX))?;
+            if last_time.is_some_and(|last| time <= last) {
+                return Err(format!("{}: logical time does not increase", path));
+            }
+            last_time = Some(time);
+            for (name, hash) in [
+                ("cause", &step.cause),
+                ("effect", &step.effect),
+                ("proof", &step.proof),
+            ] {
+                Digest::parse(hash).map_err(|e| format!("{}: {} hash: {}", path, name, e))?;
+            }
+            if let Some(reference) = &step.constraints {
+                if self.constraints.as_ref().map(|c| &c.id) != Some(reference) {
+                    return Err(format!("{}: unknown constraints {}", path, reference));
+                }
+            }
+        }
+        let head = &self.steps[self.steps.len() - 1];
+        if head.effect != self.state {
+            return Err(format!(
+                "step {}: effect {} is not the head state {}",
+                head.id, head.effect, self.state
+            ));
+        }
+        if self.heap_encoding == HEAP_ENCODING {
+            let heap = Pack::decode(self.heap.as_bytes())
+                .and_then(|pack| pack.verify().map(|()| pack))
+                .map_err(|e| format!("heap: {}", e))?;
+            if Digest::of(state.algorithm, &heap.lane) != state {
+                return Err("heap does not hash to the state hash".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Replays the ledger from the initial state of `object`. The error names
+    /// the first step whose replay differs from the record.
+    pub fn replay<'a>(&self, object: &'a Object) -> Result<Executor<'a>, String> {
+        self.check_continuity()?;
+        let mut executor = Executor::new(object);
+        for step in &self.steps {
+            let at = format!("step {} ({})", step.id, step.time);
+            let cause = Digest::parse(&step.cause).map_err(|e| format!("{}: {}", at, e))?;
+            let event = object
+                .steps
+                .iter()
+                .map(|s| &s.cause)
+                .find(|c| Digest::of(cause.algorithm, c.as_bytes()) == cause)
+                .ok_or_else(|| {
+                    format!(
+                        "{}: cause {} is no event of {}",
+                        at, step.cause, object.object_id
+                    )
+                })?;
+            let record = executor
+                .apply(event)
+                .map_err(|r| format!("{}: {} rejected on replay: {}", at, event, r.reason))?;
+            if logical_time(&step.time) != Some(record.time) {
+                return Err(format!(
+                    "{}: replayed time is {}{}",
+                    at, TIME_PREFIX, record.time
+                ));
+            }
+            if record.state.to_string() != step.effect {
+                return Err(format!(
+                    "{}: replayed state {} differs from effect {}",
+                    at, record.state, step.effect
+                ));
+            }
+            if record.proof.to_string() != step.proof {
+                return Err(format!(
+                    "{}: replayed proof {} differs from proof {}",
+                    at, record.proof, step.proof
+                ));
+            }
+        }
+        let head = executor.state_hash().to_string();
+        if head != self.state {
+            return Err(format!(
+                "replayed head state {} differs from state {}",
+                head, self.state
+            ));
+        }
+        Ok(executor)
+    }
+
+    /// Projects the committed history of `executor`. An empty history has no
+    /// IDB.xml: the schema requires at least one step.
+    pub fn project(executor: &Executor) -> Result<Idb, String> {
+        if executor.log().is_empty() {
+            return Err("no committed causal steps to record".to_string());
+        }
+        let object = executor.object();
+        let invariants: Vec<String> = object
+            .state
+            .values()
+            .flat_map(|field| field.invariants.iter().cloned())
+            .collect();
+        let constraints = (!invariants.is_empty()).then(|| Constraints {
+            id: format!("{}{}", CONSTRAINTS_ID_PREFIX, object.object_id),
+            invariants,
+        });
+        let steps = executor
+            .log()
+            .iter()
+            .map(|step| Step {
+                id: format!("{}{}", STEP_ID_PREFIX, step.time),
+                time: format!("{}{}", TIME_PREFIX, step.time),
+                cause: step.cause.to_string(),
+                effect: step.state.to_string(),
+                proof: step.proof.to_string(),
+                constraints: constraints.as_ref().map(|c| c.id.clone()),
+            })
+            .collect();
+        Ok(Idb {
+            version: IDB_VERSION.to_string(),
+            state: executor.state_hash().to_string(),
+            heap_encoding: HEAP_ENCODING.to_string(),
+            heap: heap(executor).encode(),
+            steps,
+            constraints,
+        })
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<idb version=\"{}\" xmlns=\"{}\">\n",
+            escape(&self.version),
+            IDB_NAMESPACE
+        ));
+        out.push_str(&format!("  <state hash=\"{}\">\n", escape(&self.state)));
+        out.push_str(&format!(
+            "    <heap encoding=\"{}\">{}</heap>\n",
+            escape(&self.heap_encoding),
+            escape(&self.heap)
+        ));
+        out.push_str("  </state>\n  <causal>\n");
+        for step in &self.steps {
+            out.push_str(&format!(
+                "    <step id=\"{}\" time=\"{}\">\n",
+                escape(&step.id),
+                escape(&step.time)
+            ));
+            for (name, hash) in [
+                ("cause", &step.cause),
+                ("effect", &step.effect),
+                ("proof", &step.proof),
+            ] {
+                out.push_str(&format!("      <{} hash=\"{}\"/>\n", name, escape(hash)));
+            }
+            if let Some(reference) = &step.constraints {
+                out.push_str(&format!(
+                    "      <constraints ref=\"{}\"/>\n",
+                    escape(reference)
+                ));
+            }
+            out.push_str("    </step>\n");
+        }
+        out.push_str("  </causal>\n");
+        if let Some(constraints) = &self.constraints {
+            out.push_str(&format!(
+                "  <constraints id=\"{}\">\n",
+                escape(&constraints.id)
+            ));
+            for invariant in &constraints.invariants {
+                out.push_str(&format!(
+                    "    <invariant>{}</invariant>\n",
+                    escape(invariant)
+                ));
+            }
+            out.push_str("  </constraints>\n");
+        }
+        out.push_str("</idb>\n");
+        out
+    }
+}
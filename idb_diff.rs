@@ -0,0 +1,279 @@
+// idb_diff.rs
+// IDB Ledger Diff (append-only check)
+// Version: v1
+//
+// Classifies the difference between two versions of an IDB.xml ledger. IDB.xml
+// is append-only, so a legitimate new version keeps every old step in place
+// and unchanged, may append steps, may add a constraint block where there was
+// none, and may move the head state only through the appended steps. Anything
+// else is illegal: reordered, edited, removed or inserted steps, a head state
+// or heap changed without a step, edited or removed constraints, or a new
+// schema version.
+//
+// A head moved by appended steps is legal only when the new ledger replays
+// against the object that produced it and the old head is the state just
+// before the first appended step. Without the object the move is reported as
+// unverified, never as legal.
+
+use serde_json::Value;
+
+use crate::idb::{Idb, Step};
+use crate::scxq7_object::Object;
+
+pub const DIFF_SCHEMA: &str = "idb.diff.v1";
+pub const UNVERIFIED: &str = "state.unverified";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub kind: &'static str,
+    pub subject: String,
+    pub detail: String,
+    pub legal: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diff {
+    pub old_steps: usize,
+    pub new_steps: usize,
+    pub old_state: String,
+    pub new_state: String,
+    pub changes: Vec<Change>,
+}
+
+fn change(kind: &'static str, subject: &str, detail: String, legal: bool) -> Change {
+    Change {
+        kind,
+        subject: subject.to_string(),
+        detail,
+        legal,
+    }
+}
+
+/// Fields of `new` that differ from `old`.
+fn edited_fields(old: &Step, new: &Step) -> Vec<&'static str> {
+    [
+        ("time", old.time == new.time),
+        ("cause", old.cause == new.cause),
+        ("effect", old.effect == new.effect),
+        ("proof", old.proof == new.proof),
+        ("constraints", old.constraints == new.constraints),
+    ]
+    .into_iter()
+    .filter(|(_, same)| !same)
+    .map(|(field, _)| field)
+    .collect()
+}
+
+fn quoted(text: &str) -> String {
+    Value::String(text.to_string()).to_string()
+}
+
+/// Checks that the appended steps of `new` carry the old head to the new one:
+/// the whole ledger replays against `object`, and the step before the first
+/// appended one ends at the old head.
+fn reachable(old: &Idb, new: &Idb, object: &Object) -> Result<(), String> {
+    new.replay(object)?;
+    let before = old
+        .steps
+        .len()
+        .checked_sub(1)
+        .and_then(|last| new.steps.get(last));
+    match before {
+        Some(step) if step.effect == old.state => Ok(()),
+        Some(step) => Err(format!(
+            "step {} ends at {}, not at the old head {}",
+            step.id, step.effect, old.state
+        )),
+        None => Err("no step before the appended steps".to_string()),
+    }
+}
+
+pub fn diff(old: &Idb, new: &Idb, object: Option<&Object>) -> Diff {
+    let mut changes = Vec::new();
+
+    if old.version != new.version {
+        changes.push(change(
+            "version.changed",
+            "idb",
+            format!("{} → {}", old.version, new.version),
+            false,
+        ));
+    }
+
+    for (i, step) in old.steps.iter().enumerate() {
+        match new.steps.iter().position(|s| s.id == step.id) {
+            None => changes.push(change(
+                "step.removed",
+                &step.id,
+                format!("was at position {}", i + 1),
+                false,
+            )),
+            Some(j) => {
+                if j != i {
+                    changes.push(change(
+                        "step.reordered",
+                        &step.id,
+                        format!("position {} → {}", i + 1, j + 1),
+                        false,
+                    ));
+                }
+                let fields = edited_fields(step, &new.steps[j]);
+                if !fields.is_empty() {
+                    changes.push(change("step.edited", &step.id, fields.join(", "), false));
+                }
+            }
+        }
+    }
+    let mut appended = 0;
+    for (j, step) in new.steps.iter().enumerate() {
+        if old.steps.iter().any(|s| s.id == step.id) {
+            continue;
+        }
+        if j >= old.steps.len() {
+            appended += 1;
+            changes.push(change(
+                "step.appended",
+                &step.id,
+                format!("{} effect {}", step.time, step.effect),
+                true,
+            ));
+        } else {
+            changes.push(change(
+                "step.inserted",
+                &step.id,
+                format!("at position {} of {}", j + 1, old.steps.len()),
+                false,
+            ));
+        }
+    }
+
+    if old.state != new.state {
+        changes.push(match object {
+            _ if appended == 0 => change(
+                "state.edited",
+                &new.state,
+                format!("from {} without a causal step", old.state),
+                false,
+            ),
+            None => change(
+                UNVERIFIED,
+                &new.state,
+                format!(
+                    "from {} through {} appended steps, not replayed (no object)",
+                    old.state, appended
+                ),
+                false,
+            ),
+            Some(object) => match reachable(old, new, object) {
+                Ok(()) => change(
+                    "state.advanced",
+                    &new.state,
+                    format!("from {} through {} appended steps", old.state, appended),
+                    true,
+                ),
+                Err(reason) => change("state.unreachable", &new.state, reason, false),
+            },
+        });
+    } else if old.heap != new.heap || old.heap_encoding != new.heap_encoding {
+        changes.push(change(
+            "heap.edited",
+            &new.state,
+            "heap changed under the same state hash".to_string(),
+            false,
+        ));
+    }
+
+    match (&old.constraints, &new.constraints) {
+        (None, None) => {}
+        (None, Some(added)) => changes.push(change(
+            "constraints.added",
+            &added.id,
+            format!("{} invariants", added.invariants.len()),
+            true,
+        )),
+        (Some(removed), None) => changes.push(change(
+            "constraints.removed",
+            &removed.id,
+            String::new(),
+            false,
+        )),
+        (Some(before), Some(after)) if before.id != after.id => {
+            changes.push(change(
+                "constraints.removed",
+                &before.id,
+                format!("replaced by {}", after.id),
+                false,
+            ));
+        }
+        (Some(before), Some(after)) if before.invariants != after.invariants => {
+            changes.push(change(
+                "constraints.edited",
+                &before.id,
+                "invariants modified".to_string(),
+                false,
+            ));
+        }
+        _ => {}
+    }
+
+    Diff {
+        old_steps: old.steps.len(),
+        new_steps: new.steps.len(),
+        old_state: old.state.clone(),
+        new_state: new.state.clone(),
+        changes,
+    }
+}
+
+impl Diff {
+    pub fn illegal(&self) -> Option<&Change> {
+        self.changes
+            .iter()
+            .find(|c| !c.legal && c.kind != UNVERIFIED)
+    }
+
+    pub fn unverified(&self) -> Option<&Change> {
+        self.changes.iter().find(|c| c.kind == UNVERIFIED)
+    }
+
+    pub fn verdict(&self) -> &'static str {
+        if self.illegal().is_some() {
+            "ILLEGAL"
+        } else if self.unverified().is_some() {
+            "UNVERIFIED"
+        } else {
+            "APPEND_ONLY"
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let changes = self
+            .changes
+            .iter()
+            .map(|c| {
+                format!(
+                    "    {{\"kind\": \"{}\", \"subject\": {}, \"detail\": {}, \"legal\": {}}}",
+                    c.kind,
+                    quoted(&c.subject),
+                    quoted(&c.detail),
+                    c.legal
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        format!(
+            "{{\n  \"@diff\": \"{}\",\n  \"old\": {{\"steps\": {}, \"state\": {}}},\n  \"new\": {{\"steps\": {}, \"state\": {}}},\n  \"changes\": {},\n  \"verdict\": \"{}\"\n}}\n",
+            DIFF_SCHEMA,
+            self.old_steps,
+            quoted(&self.old_state),
+            self.new_steps,
+            quoted(&self.new_state),
+            if changes.is_empty() {
+                "[]".to_string()
+            } else {
+                format!("[\n{}\n  ]", changes)
+            },
+            self.verdict()
+        )
+    }
+}
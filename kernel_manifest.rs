@@ -0,0 +1,139 @@
+// kernel_manifest.rs
+// Kernel Manifest Validation against Collapse Geometry Axioms
+// Version: v1
+//
+// A kernel manifest declares the collapse geometry it runs on and the algebraic
+// properties it relies on. Those declarations must not claim more than the
+// geometry (axioms/collapse.geometry.schema.xjson) provides; fields the axioms
+// say nothing about are not judged here.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde_json::Value;
+
+pub const GEOMETRY_SCHEMA_FILE: &str = "collapse.geometry.schema.xjson";
+pub const MANIFEST_SUFFIX: &str = "kernel.manifest.xjson";
+
+#[derive(Debug, Clone)]
+pub struct GeometryProperties {
+    pub associative: bool,
+    pub locality: String,
+    pub merge: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeometryAxioms {
+    pub properties: BTreeMap<String, GeometryProperties>,
+}
+
+impl GeometryAxioms {
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let bytes = fs::read(path).map_err(|_| format!("cannot read {}", path.display()))?;
+        let doc: Value = serde_json::from_slice(&bytes)
+            .map_err(|e| format!("invalid JSON in {} ({})", path.display(), e))?;
+        Self::from_schema(&doc)
+    }
+
+    pub fn from_schema(doc: &Value) -> Result<Self, String> {
+        let values = doc
+            .pointer("/collapse_geometry/values")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "geometry schema missing collapse_geometry.values".to_string())?;
+        let mut properties = BTreeMap::new();
+        for value in values {
+            let name = value
+                .as_str()
+                .ok_or_else(|| "geometry schema: non-string geometry name".to_string())?;
+            let props = doc
+                .get("geometry_properties")
+                .and_then(|p| p.get(name))
+                .ok_or_else(|| format!("geometry schema: no properties for {}", name))?;
+            let associative = props
+                .get("associative")
+                .and_then(Value::as_bool)
+                .ok_or_else(|| format!("geometry schema: {}.associative must be boolean", name))?;
+            let text = |key: &str| {
+                props
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_string()
+            };
+            properties.insert(
+                name.to_string(),
+                GeometryProperties {
+                    associative,
+                    locality: text("locality"),
+                    merge: text("merge"),
+                },
+            );
+        }
+        Ok(GeometryAxioms { properties })
+    }
+}
+
+pub fn is_kernel_manifest(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.ends_with(MANIFEST_SUFFIX))
+        .unwrap_or(false)
+}
+
+fn flag(section: &Value, section_name: &str, key: &str) -> Result<Option<bool>, String> {
+    match section.get(key) {
+        None => Ok(None),
+        Some(Value::Bool(b)) => Ok(Some(*b)),
+        Some(_) => Err(format!("{}.{} must be boolean", section_name, key)),
+    }
+}
+
+/// Checks one kernel manifest against the geometry axioms. The error names the
+/// offending field path.
+pub fn validate(manifest: &Value, axioms: &GeometryAxioms) -> Result<(), String> {
+    if manifest.get("@kernel").and_then(Value::as_str).is_none() {
+        return Err("@kernel missing".to_string());
+    }
+
+    let collapse = manifest
+        .get("@collapse")
+        .filter(|c| c.is_object())
+        .ok_or_else(|| "@collapse missing".to_string())?;
+    let geometry = collapse
+        .get("geometry")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "@collapse.geometry missing".to_string())?;
+    let props = axioms
+        .properties
+        .get(geometry)
+        .ok_or_else(|| format!("@collapse.geometry: unknown geometry {}", geometry))?;
+
+    if flag(collapse, "@collapse", "associativity_required")? == Some(true) && !props.associative {
+        return Err(format!(
+            "@collapse.associativity_required: {} is non-associative",
+            geometry
+        ));
+    }
+
+    // Parallel evaluation regroups subranges, which is only lawful when the merge is associative.
+    if flag(collapse, "@collapse", "parallel_safe")? == Some(true) && !props.associative {
+        return Err(format!(
+            "@collapse.parallel_safe: {} merge ({}) cannot be regrouped",
+            geometry, props.merge
+        ));
+    }
+
+    if let Some(accepts) = manifest.get("@accepts") {
+        if accepts.get("term_law").and_then(Value::as_str) == Some("associative")
+            && !props.associative
+        {
+            return Err(format!(
+                "@accepts.term_law: {} is non-associative",
+                geometry
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,205 @@
+// pi_adapter.rs
+// π-Adapter Output (pi.signal.v1)
+// Version: v1
+//
+// Validator for the canonical adapter output object of
+// docs/pi-adapter-interface-v1.md. Every rule is a hard rule; errors name the
+// offending JSON pointer. Fields outside the contract are rejected, and
+// model representations (logits, token ids, embeddings, text) are refused by
+// name.
+
+use std::f64::consts::TAU;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::hash::Digest;
+
+pub const SIGNAL_TYPE: &str = "pi.signal.v1";
+pub const SIGNAL_VERSION: &str = "1.0";
+pub const SIGNAL_SUFFIX: &str = ".pi.signal.json";
+
+const TOP_FIELDS: [&str; 4] = ["@type", "@version", "geometry", "provenance"];
+const GEOMETRY_FIELDS: [&str; 4] = ["angles", "magnitudes", "paths", "epsilon"];
+const PROVENANCE_FIELDS: [&str; 4] = ["adapter", "adapter_version", "deterministic", "source_hash"];
+const FORBIDDEN_FIELDS: [(&str, &str); 8] = [
+    ("logits", "logits"),
+    ("token_ids", "token ids"),
+    ("tokens", "token ids"),
+    ("embedding", "embeddings"),
+    ("embeddings", "embeddings"),
+    ("text", "text"),
+    ("prompt", "text"),
+    ("completion", "text"),
+];
+
+pub fn is_signal(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(|n| n.ends_with(SIGNAL_SUFFIX))
+        .unwrap_or(false)
+}
+
+fn object<'a>(value: &'a Value, path: &str) -> Result<&'a Map<String, Value>, String> {
+    value.as_object().ok_or_else(|| {
+        format!(
+            "{}: must be an object",
+            if path.is_empty() { "/" } else { path }
+        )
+    })
+}
+
+/// Refuses forbidden representations by name, then any field not in `allowed`.
+fn known_fields(map: &Map<String, Value>, path: &str, allowed: &[&str]) -> Result<(), String> {
+    for key in map.keys() {
+        if let Some((_, what)) = FORBIDDEN_FIELDS.iter().find(|(name, _)| name == key) {
+            return Err(format!("{}/{}: {} are forbidden", path, key, what));
+        }
+    }
+    for key in map.keys() {
+        if !allowed.contains(&key.as_str()) {
+            return Err(format!("{}/{}: unknown field", path, key));
+        }
+    }
+    Ok(())
+}
+
+fn required<'a>(map: &'a Map<String, Value>, path: &str, key: &str) -> Result<&'a Value, String> {
+    map.get(key)
+        .ok_or_else(|| format!("{}/{}: missing", path, key))
+}
+
+fn numbers(value: &Value, path: &str) -> Result<Vec<f64>, String> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| format!("{}: must be an array", path))?;
+    items
+        .iter()
+        .enumerate()
+        .map(|(i, item)| {
+            item.as_f64()
+                .filter(|n| n.is_finite())
+                .ok_or_else(|| format!("{}/{}: must be a finite number", path, i))
+        })
+        .collect()
+}
+
+fn validate_geometry(value: &Value) -> Result<(), String> {
+    const PATH: &str = "/geometry";
+    let geometry = object(value, PATH)?;
+    known_fields(geometry, PATH, &GEOMETRY_FIELDS)?;
+
+    let angles = numbers(required(geometry, PATH, "angles")?, "/geometry/angles")?;
+    for (i, angle) in angles.iter().enumerate() {
+        if !(0.0..TAU).contains(angle) {
+            return Err(format!(
+                "/geometry/angles/{}: {} is outside [0, 2π)",
+                i, angle
+            ));
+        }
+    }
+
+    let magnitudes = numbers(
+        required(geometry, PATH, "magnitudes")?,
+        "/geometry/magnitudes",
+    )?;
+    for (i, magnitude) in magnitudes.iter().enumerate() {
+        if *magnitude < 0.0 {
+            return Err(format!(
+                "/geometry/magnitudes/{}: {} is negative",
+                i, magnitude
+            ));
+        }
+    }
+    if angles.len() != magnitudes.len() {
+        return Err(format!(
+            "/geometry/magnitudes: length {} differs from angles length {}",
+            magnitudes.len(),
+            angles.len()
+        ));
+    }
+
+    if let Some(paths) = geometry.get("paths") {
+        let paths = paths
+            .as_array()
+            .ok_or_else(|| "/geometry/paths: must be an array".to_string())?;
+        for (i, nodes) in paths.iter().enumerate() {
+            let nodes = nodes
+                .as_array()
+                .ok_or_else(|| format!("/geometry/paths/{}: must be an array", i))?;
+            for (j, node) in nodes.iter().enumerate() {
+                let index = node
+                    .as_u64()
+                    .ok_or_else(|| format!("/geometry/paths/{}/{}: must be an index", i, j))?;
+                if index >= angles.len() as u64 {
+                    return Err(format!(
+                        "/geometry/paths/{}/{}: index {} out of range (0..{})",
+                        i,
+                        j,
+                        index,
+                        angles.len()
+                    ));
+                }
+            }
+        }
+    }
+
+    let epsilon = required(geometry, PATH, "epsilon")?
+        .as_f64()
+        .ok_or_else(|| "/geometry/epsilon: must be a number".to_string())?;
+    if !(epsilon.is_finite() && epsilon > 0.0) {
+        return Err(format!(
+            "/geometry/epsilon: {} must be a positive angle",
+            epsilon
+        ));
+    }
+    Ok(())
+}
+
+fn validate_provenance(value: &Value) -> Result<(), String> {
+    const PATH: &str = "/provenance";
+    let provenance = object(value, PATH)?;
+    known_fields(provenance, PATH, &PROVENANCE_FIELDS)?;
+
+    for key in ["adapter", "adapter_version"] {
+        let text = required(provenance, PATH, key)?
+            .as_str()
+            .ok_or_else(|| format!("{}/{}: must be a string", PATH, key))?;
+        if text.is_empty() {
+            return Err(format!("{}/{}: must not be empty", PATH, key));
+        }
+    }
+    if required(provenance, PATH, "deterministic")? != &Value::Bool(true) {
+        return Err("/provenance/deterministic: must be true".to_string());
+    }
+    let source_hash = required(provenance, PATH, "source_hash")?
+        .as_str()
+        .ok_or_else(|| "/provenance/source_hash: must be a string".to_string())?;
+    Digest::parse(source_hash).map_err(|e| format!("/provenance/source_hash: {}", e))?;
+    Ok(())
+}
+
+/// Checks one adapter output object against every rule of π-Adapter Interface v1.
+pub fn validate(doc: &Value) -> Result<(), String> {
+    let top = object(doc, "")?;
+    known_fields(top, "", &TOP_FIELDS)?;
+
+    let signal_type = required(top, "", "@type")?;
+    if signal_type.as_str() != Some(SIGNAL_TYPE) {
+        return Err(format!("/@type: must be {}", SIGNAL_TYPE));
+    }
+    let version = required(top, "", "@version")?;
+    if version.as_str() != Some(SIGNAL_VERSION) {
+        return Err(format!("/@version: must be {}", SIGNAL_VERSION));
+    }
+    validate_geometry(required(top, "", "geometry")?)?;
+    validate_provenance(required(top, "", "provenance")?)
+}
+
+/// Parses and validates adapter output bytes, returning the document.
+pub fn load(bytes: &[u8]) -> Result<Value, String> {
+    let doc: Value =
+        serde_json::from_slice(bytes).map_err(|_| format!("{} is not valid JSON", SIGNAL_TYPE))?;
+    validate(&doc)?;
+    Ok(doc)
+}
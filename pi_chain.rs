@@ -0,0 +1,207 @@
+// pi_chain.rs
+// π-GCCP Hyperbolic Chain (π-gccp.hyperbolic-chain.v1)
+// Version: v1
+//
+// Ordered chains of π-signal lanes per docs/pi-gccp-hyperbolic-chaining-v1.md.
+// Every node must be a saddle (curvature < 0) and the chain as a whole must
+// preserve orientation. The fold composes phase (Σ mod 2π), curvature (Σ,
+// exact), orientation (xor) and closure (AND); it collapses only when every
+// link is closed. Links form a hash chain, link_i = blake3(link_{i-1} ‖
+// lane_i), so reordering any two lanes changes every later link and the root.
+
+use std::f64::consts::TAU;
+
+use serde_json::Value;
+
+use crate::exact::Ratio;
+use crate::hash::{Algorithm, Digest, Hasher};
+use crate::pi_signal::PiSignalView;
+
+pub const CHAIN_THEOREM: &str = "π-gccp.hyperbolic-chain.v1";
+pub const PROOF_TYPE: &str = "π-gccp.hyperbolic-chain.proof.v1";
+pub const MIN_LENGTH: usize = 2;
+pub const LINK_HASH: Algorithm = Algorithm::Blake3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    pub lane_hash: Digest,
+    /// Running composition after this link.
+    pub phase: f64,
+    pub curvature: Ratio,
+    pub orientation: i8,
+    pub closed: bool,
+    pub digest: Digest,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chain {
+    pub links: Vec<Link>,
+    pub collapse: Option<Ratio>,
+}
+
+fn link_digest(prev: Option<&Digest>, lane_hash: &Digest) -> Digest {
+    let mut hasher = Hasher::new(LINK_HASH);
+    hasher.update(&prev.map(|d| d.bytes).unwrap_or([0u8; 32]));
+    hasher.update(&lane_hash.bytes);
+    hasher.finalize()
+}
+
+impl Chain {
+    /// Validates and folds the lanes in the order given.
+    pub fn fold(lanes: &[Vec<u8>]) -> Result<Chain, String> {
+        if lanes.len() < MIN_LENGTH {
+            return Err(format!("chain needs at least {} links", MIN_LENGTH));
+        }
+        let mut links: Vec<Link> = Vec::new();
+        let mut mass = Some(Ratio::from_f64(0.0)?);
+        for (i, lane) in lanes.iter().enumerate() {
+            let view = PiSignalView::new(lane).map_err(|e| format!("link {}: {}", i, e))?;
+            view.verify().map_err(|e| format!("link {}: {}", i, e))?;
+            let signal = view.to_signal();
+            if signal.curvature >= 0.0 {
+                return Err(format!(
+                    "link {}: curvature {} is not negative (per-node saddle)",
+                    i, signal.curvature
+                ));
+            }
+
+            let prev = links.last();
+            let curvature = Ratio::from_f64(signal.curvature)?;
+            let lane_hash = view.lane_hash();
+            let link = Link {
+                phase: (prev.map(|l| l.phase).unwrap_or(0.0) + signal.phase).rem_euclid(TAU),
+                curvature: match prev {
+                    Some(l) => l.curvature.add(&curvature),
+                    None => curvature,
+                },
+                orientation: prev.map(|l| l.orientation).unwrap_or(1) * signal.orientation,
+                closed: prev.map(|l| l.closed).unwrap_or(true) && signal.closed,
+                digest: link_digest(prev.map(|l| &l.digest), &lane_hash),
+                lane_hash,
+            };
+            mass = match mass {
+                Some(sum) if signal.closed => Some(
+                    sum.add(&Ratio::from_f64(signal.mass_geometric)?)
+                        .add(&Ratio::from_f64(signal.mass_algebraic)?),
+                ),
+                _ => None,
+            };
+            links.push(link);
+        }
+
+        let last = links.last().expect("chain has links");
+        if last.orientation != 1 {
+            return Err(
+                "chain reverses orientation (global invariant: mobius-orientation-preserving)"
+                    .to_string(),
+            );
+        }
+        if let Some(exact) = &mass {
+            exact.to_f64()?;
+        }
+        Ok(Chain {
+            links,
+            collapse: mass,
+        })
+    }
+
+    pub fn root(&self) -> &Digest {
+        &self.links.last().expect("chain has links").digest
+    }
+
+    pub fn value(&self) -> Option<f64> {
+        self.collapse
+            .as_ref()
+            .map(|exact| exact.to_f64().expect("checked when the chain was folded"))
+    }
+
+    /// Canonical chain proof: one proof line per link, then the root and the
+    /// collapse (or the preserved saddle state).
+    pub fn render(&self) -> String {
+        let links = self
+            .links
+            .iter()
+            .enumerate()
+            .map(|(i, link)| {
+                format!(
+                    "    {{\"index\":{},\"lane\":\"{}\",\"phase\":\"0x{:016x}\",\"curvature\":\"{}\",\"orientation\":{},\"closure\":{},\"link\":\"{}\"}}",
+                    i,
+                    link.lane_hash,
+                    link.phase.to_bits(),
+                    link.curvature,
+                    link.orientation,
+                    link.closed,
+                    link.digest
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        let (state, collapse) = match (&self.collapse, self.value()) {
+            (Some(exact), Some(value)) => (
+                "collapsed",
+                format!(
+                    "{{\"exact\":\"{}\",\"bits\":\"0x{:016x}\",\"decimal\":\"{:?}\"}}",
+                    exact,
+                    value.to_bits(),
+                    value
+                ),
+            ),
+            _ => ("saddle-preserved", "null".to_string()),
+        };
+        format!(
+            "{{\n  \"@proof\": \"{}\",\n  \"@theorem\": \"{}\",\n  \"links\": [\n{}\n  ],\n  \"root\": \"{}\",\n  \"state\": \"{}\",\n  \"collapse\": {}\n}}\n",
+            PROOF_TYPE,
+            CHAIN_THEOREM,
+            links,
+            self.root(),
+            state,
+            collapse
+        )
+    }
+
+    /// Replays a recorded chain proof against the lanes in order: each link
+    /// must bind the lane at its position, then the whole proof must match.
+    pub fn verify(proof: &[u8], lanes: &[Vec<u8>]) -> Result<Chain, String> {
+        let doc: Value = serde_json::from_slice(proof)
+            .map_err(|_| "chain proof is not valid JSON".to_string())?;
+        if doc.get("@proof").and_then(Value::as_str) != Some(PROOF_TYPE) {
+            return Err("chain proof has wrong @proof".to_string());
+        }
+        let recorded = doc
+            .get("links")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "chain proof missing links".to_string())?;
+        if recorded.len() != lanes.len() {
+            return Err(format!(
+                "chain proof has {} links, {} lanes given",
+                recorded.len(),
+                lanes.len()
+            ));
+        }
+
+        let chain = Chain::fold(lanes)?;
+        for (i, (entry, link)) in recorded.iter().zip(&chain.links).enumerate() {
+            let lane = entry
+                .get("lane")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if lane != link.lane_hash.to_string() {
+                return Err(format!("link {}: lane out of order or altered", i));
+            }
+            let digest = entry
+                .get("link")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if digest != link.digest.to_string() {
+                return Err(format!("link {}: link hash mismatch", i));
+            }
+        }
+        if doc.get("root").and_then(Value::as_str) != Some(chain.root().to_string().as_str()) {
+            return Err("chain root mismatch".to_string());
+        }
+        if proof != chain.render().as_bytes() {
+            return Err("chain proof differs from replay".to_string());
+        }
+        Ok(chain)
+    }
+}
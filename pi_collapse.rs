@@ -0,0 +1,232 @@
+// pi_collapse.rs
+// SCXQ2 π-Collapse Proof (scxq2.pi-collapse.v1)
+// Version: v1
+//
+// Collapse receipts per docs/scxq2-proof-pi-collapse-v1.md. Collapsing a
+// π-signal lane records the lane hash, the result of each operation in order
+// and the output value as raw f64 bits. Replay re-executes the operations on
+// the lane and accepts only bit-identical results (tolerance 0).
+
+use std::f64::consts::TAU;
+
+use serde_json::Value;
+
+use crate::hash::Digest;
+use crate::pi_signal::{self, PiSignalView};
+
+pub const PROOF_TYPE: &str = "scxq2.pi-collapse.v1";
+pub const PROOF_KIND: &str = "execution-proof";
+pub const OPERATIONS: [&str; 4] = [
+    "phase-normalize",
+    "orientation-check",
+    "closure-test",
+    "mass-split",
+];
+pub const OUTPUT_TYPE: &str = "f64";
+pub const OUTPUT_EXPECTED: &str = "π + finite_residue";
+pub const VERIFICATION_METHOD: &str = "deterministic-replay";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub op: String,
+    pub result: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Receipt {
+    pub lane_hash: Digest,
+    pub steps: Vec<Step>,
+    pub output_bits: u64,
+}
+
+fn bits(value: f64) -> String {
+    format!("0x{:016x}", value.to_bits())
+}
+
+fn parse_bits(text: &str) -> Option<u64> {
+    let hex = text.strip_prefix("0x")?;
+    if hex.len() != 16 || !hex.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return None;
+    }
+    u64::from_str_radix(hex, 16).ok()
+}
+
+/// Distance between two f64 values in units in the last place.
+pub fn ulp_distance(a: f64, b: f64) -> u64 {
+    let ordered = |x: f64| {
+        let raw = x.to_bits() as i64;
+        if raw < 0 {
+            i64::MIN - raw
+        } else {
+            raw
+        }
+    };
+    ordered(a).abs_diff(ordered(b))
+}
+
+fn str_at<'a>(doc: &'a Value, path: &str) -> Result<&'a str, String> {
+    doc.pointer(path)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("{} missing", path))
+}
+
+fn expect_str(doc: &Value, path: &str, expected: &str) -> Result<(), String> {
+    let found = str_at(doc, path)?;
+    if found != expected {
+        return Err(format!("{}: {} is not {}", path, found, expected));
+    }
+    Ok(())
+}
+
+impl Receipt {
+    /// Verifies the lane, executes the collapse operations and records each
+    /// result. Open lanes are illegal and yield no receipt.
+    pub fn collapse(lane: &[u8]) -> Result<Receipt, String> {
+        let view = PiSignalView::new(lane)?;
+        view.verify()?;
+
+        let phase = view.phase().rem_euclid(TAU);
+        let orientation = view.orientation();
+        let value = view.collapse()?;
+
+        let results = [
+            bits(phase),
+            format!("{:+}", orientation),
+            "closed".to_string(),
+            bits(value),
+        ];
+        let steps = OPERATIONS
+            .iter()
+            .zip(results)
+            .map(|(op, result)| Step {
+                op: op.to_string(),
+                result,
+            })
+            .collect();
+        Ok(Receipt {
+            lane_hash: view.lane_hash(),
+            steps,
+            output_bits: value.to_bits(),
+        })
+    }
+
+    pub fn value(&self) -> f64 {
+        f64::from_bits(self.output_bits)
+    }
+
+    /// Canonical receipt text. The same lane always yields identical bytes.
+    pub fn render(&self) -> String {
+        let operations = self
+            .steps
+            .iter()
+            .map(|step| {
+                format!(
+                    "    {{\"op\":\"{}\",\"exact\":true,\"result\":\"{}\"}}",
+                    step.op, step.result
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        format!(
+            "{{\n  \"@proof\": \"{}\",\n  \"@type\": \"{}\",\n  \"inputs\": [\n    {{\"lane\":\"{}\",\"hash\":\"{}\"}}\n  ],\n  \"operations\": [\n{}\n  ],\n  \"outputs\": {{\"value\":\"{}\",\"bits\":\"0x{:016x}\",\"decimal\":\"{:?}\",\"expected\":\"{}\"}},\n  \"verification\": {{\"method\":\"{}\",\"tolerance\":0}}\n}}\n",
+            PROOF_TYPE,
+            PROOF_KIND,
+            pi_signal::LANE_ID,
+            self.lane_hash,
+            operations,
+            OUTPUT_TYPE,
+            self.output_bits,
+            self.value(),
+            OUTPUT_EXPECTED,
+            VERIFICATION_METHOD
+        )
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Receipt, String> {
+        let doc: Value =
+            serde_json::from_slice(bytes).map_err(|_| "receipt is not valid JSON".to_string())?;
+        expect_str(&doc, "/@proof", PROOF_TYPE)?;
+        expect_str(&doc, "/@type", PROOF_KIND)?;
+
+        let inputs = doc
+            .get("inputs")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "/inputs missing".to_string())?;
+        if inputs.len() != 1 {
+            return Err("/inputs must name exactly one lane".to_string());
+        }
+        expect_str(&doc, "/inputs/0/lane", pi_signal::LANE_ID)?;
+        let lane_hash = Digest::parse(str_at(&doc, "/inputs/0/hash")?)
+            .map_err(|e| format!("/inputs/0/hash: {}", e))?;
+        lane_hash
+            .expect(pi_signal::FOOTER_HASH)
+            .map_err(|e| format!("/inputs/0/hash: {}", e))?;
+
+        let operations = doc
+            .get("operations")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "/operations missing".to_string())?;
+        if operations.len() != OPERATIONS.len() {
+            return Err(format!("/operations must list {}", OPERATIONS.join(", ")));
+        }
+        let mut steps = Vec::new();
+        for (i, op) in OPERATIONS.iter().enumerate() {
+            expect_str(&doc, &format!("/operations/{}/op", i), op)?;
+            if doc.pointer(&format!("/operations/{}/exact", i)) != Some(&Value::Bool(true)) {
+                return Err(format!("/operations/{}/exact must be true", i));
+            }
+            steps.push(Step {
+                op: op.to_string(),
+                result: str_at(&doc, &format!("/operations/{}/result", i))?.to_string(),
+            });
+        }
+
+        expect_str(&doc, "/outputs/value", OUTPUT_TYPE)?;
+        let output_bits = parse_bits(str_at(&doc, "/outputs/bits")?)
+            .ok_or_else(|| "/outputs/bits must be 0x and 16 lowercase hex digits".to_string())?;
+        let decimal = str_at(&doc, "/outputs/decimal")?;
+        if decimal != format!("{:?}", f64::from_bits(output_bits)) {
+            return Err("/outputs/decimal does not match /outputs/bits".to_string());
+        }
+
+        expect_str(&doc, "/verification/method", VERIFICATION_METHOD)?;
+        if doc
+            .pointer("/verification/tolerance")
+            .and_then(Value::as_u64)
+            != Some(0)
+        {
+            return Err("/verification/tolerance must be 0".to_string());
+        }
+
+        Ok(Receipt {
+            lane_hash,
+            steps,
+            output_bits,
+        })
+    }
+
+    /// Deterministic replay against the input lane: the lane hash, every
+    /// operation result and the output must match bit for bit.
+    pub fn replay(&self, lane: &[u8]) -> Result<(), String> {
+        let lane_hash = Digest::of(pi_signal::FOOTER_HASH, lane);
+        if lane_hash != self.lane_hash {
+            return Err("input lane hash mismatch".to_string());
+        }
+        let replayed = Receipt::collapse(lane).map_err(|e| format!("replay: {}", e))?;
+        if replayed.output_bits != self.output_bits {
+            return Err(format!(
+                "replay output differs by {} ULP",
+                ulp_distance(replayed.value(), self.value())
+            ));
+        }
+        for (recorded, step) in self.steps.iter().zip(&replayed.steps) {
+            if recorded.result != step.result {
+                return Err(format!(
+                    "replay diverges at {} ({} where {} was recorded)",
+                    step.op, step.result, recorded.result
+                ));
+            }
+        }
+        Ok(())
+    }
+}
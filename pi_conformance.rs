@@ -0,0 +1,500 @@
+// pi_conformance.rs
+// π-GCCP Engine Conformance Runner
+// Version: v1
+//
+// Executes docs/pi-gccp-conformance.json against any engine behind the Engine
+// trait and issues an engine conformance certificate. Each entry carries
+// PASS / FAIL / ILLEGAL only, in the result-object shape of
+// docs/scxq7-cluster-extensions-v1.md §I.3, and is hashed so the certificate
+// is SCXQ2-hashable. A forbidden practice or an approximating fallback is
+// ILLEGAL; a missing capability or a failed validation test is FAIL.
+
+use std::collections::BTreeMap;
+use std::f64::consts::PI;
+use std::fmt;
+
+use num_bigint::BigInt;
+use serde_json::Value;
+
+use crate::binary_split::{self, Evaluation};
+use crate::exact;
+use crate::hash::{Algorithm, Digest};
+use crate::pi_gccp::{self, Run};
+use crate::pi_signal::PiSignal;
+
+pub const CERTIFICATE_TYPE: &str = "π-gccp.engine-conformance.v1";
+pub const RESULT_HASH: Algorithm = Algorithm::Blake3;
+
+/// An engine under test. Capabilities are flat dotted paths, e.g.
+/// `algebra.exact-factorial`; forbidden practices are reported the same way.
+pub trait Engine {
+    fn id(&self) -> &str;
+
+    fn capabilities(&self) -> BTreeMap<String, bool>;
+
+    fn invariants(&self) -> Vec<&str>;
+
+    /// CPU fallback mode and whether it approximates, if the engine has one.
+    fn cpu_fallback(&self) -> Option<(&str, bool)>;
+
+    /// Execution profiles the engine can run under.
+    fn profiles(&self) -> Vec<&str>;
+
+    fn run(&self, profile: &str, lane: &[u8], invariant: &str) -> Result<Run, String>;
+
+    /// Exact π + 3 series collapse over `terms` terms.
+    fn series(&self, terms: u64) -> Result<Evaluation, String>;
+}
+
+/// The CPU reference engine (pi_gccp.rs) with the binary-splitting kernel.
+pub struct Reference;
+
+pub const REFERENCE_PROFILE: &str = "cpu.exact";
+
+impl Engine for Reference {
+    fn id(&self) -> &str {
+        pi_gccp::ENGINE
+    }
+
+    fn capabilities(&self) -> BTreeMap<String, bool> {
+        [
+            "geometry.phase",
+            "geometry.orientation",
+            "geometry.non-orientable-manifolds",
+            "algebra.exact-factorial",
+            "algebra.binomial-normalization",
+            "algebra.series-collapse",
+            "execution.deterministic",
+            "execution.profile-independent",
+            "execution.training-less",
+        ]
+        .into_iter()
+        .map(|name| (name.to_string(), true))
+        .collect()
+    }
+
+    fn invariants(&self) -> Vec<&str> {
+        pi_gccp::INVARIANTS.iter().map(|inv| inv.id()).collect()
+    }
+
+    fn cpu_fallback(&self) -> Option<(&str, bool)> {
+        Some(("exact-math", false))
+    }
+
+    fn profiles(&self) -> Vec<&str> {
+        vec![REFERENCE_PROFILE]
+    }
+
+    fn run(&self, profile: &str, lane: &[u8], invariant: &str) -> Result<Run, String> {
+        if profile != REFERENCE_PROFILE {
+            return Err(format!("unknown profile {}", profile));
+        }
+        let invariant = pi_gccp::invariant(invariant)
+            .ok_or_else(|| format!("unknown invariant {}", invariant))?;
+        pi_gccp::run(lane, invariant)
+    }
+
+    fn series(&self, terms: u64) -> Result<Evaluation, String> {
+        binary_split::evaluate(terms)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verdict {
+    Pass,
+    Fail,
+    Illegal,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail => write!(f, "FAIL"),
+            Verdict::Illegal => write!(f, "ILLEGAL"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TestResult {
+    pub test: String,
+    pub result: Verdict,
+    pub detail: Option<String>,
+}
+
+impl TestResult {
+    /// The result object, with `@hash` appended when given.
+    fn object(&self, hash: Option<&Digest>) -> String {
+        let detail = self
+            .detail
+            .as_deref()
+            .map(|d| {
+                format!(
+                    ",\"detail\":\"{}\"",
+                    d.replace('\\', "\\\\").replace('"', "\\\"")
+                )
+            })
+            .unwrap_or_default();
+        let hash = hash
+            .map(|h| format!(",\"@hash\":\"{}\"", h))
+            .unwrap_or_default();
+        format!(
+            "{{\"@test\":\"{}\",\"@result\":\"{}\"{}{}}}",
+            self.test, self.result, detail, hash
+        )
+    }
+
+    /// H(result): the hash of the result object without @hash.
+    pub fn hash(&self) -> Digest {
+        Digest::of(RESULT_HASH, self.object(None).as_bytes())
+    }
+}
+
+/// The parts of the conformance document the runner executes.
+#[derive(Debug, Clone)]
+pub struct Requirements {
+    pub conformance: String,
+    pub invariants: Vec<String>,
+    pub capabilities: Vec<String>,
+    pub fallback_required: bool,
+    pub fallback_mode: String,
+    pub forbidden: Vec<String>,
+    pub tests: Vec<String>,
+    pub pass: String,
+    pub fail: String,
+}
+
+fn strings(doc: &Value, pointer: &str) -> Result<Vec<String>, String> {
+    doc.pointer(pointer)
+        .and_then(Value::as_array)
+        .and_then(|items| {
+            items
+                .iter()
+                .map(|v| v.as_str().map(str::to_string))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or_else(|| format!("{}: must be an array of strings", pointer))
+}
+
+fn text(doc: &Value, pointer: &str) -> Result<String, String> {
+    doc.pointer(pointer)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| format!("{}: missing", pointer))
+}
+
+impl Requirements {
+    pub fn parse(bytes: &[u8]) -> Result<Requirements, String> {
+        let doc: Value = serde_json::from_slice(bytes)
+            .map_err(|_| "conformance document is not valid JSON".to_string())?;
+
+        let groups = doc
+            .get("required_capabilities")
+            .and_then(Value::as_object)
+            .ok_or_else(|| "/required_capabilities: missing".to_string())?;
+        let mut capabilities = Vec::new();
+        for (group, flags) in groups {
+            let flags = flags
+                .as_object()
+                .ok_or_else(|| format!("/required_capabilities/{}: must be an object", group))?;
+            for (name, required) in flags {
+                match required.as_bool() {
+                    Some(true) => capabilities.push(format!("{}.{}", group, name)),
+                    Some(false) => {}
+                    None => {
+                        return Err(format!(
+                            "/required_capabilities/{}/{}: must be boolean",
+                            group, name
+                        ))
+                    }
+                }
+            }
+        }
+
+        let tests = doc
+            .get("validation_tests")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "/validation_tests: missing".to_string())?;
+        let mut ids = Vec::new();
+        for (i, test) in tests.iter().enumerate() {
+            let id = text(test, "/id").map_err(|e| format!("/validation_tests/{}{}", i, e))?;
+            if test.get("tolerance").and_then(Value::as_f64) != Some(0.0) {
+                return Err(format!(
+                    "/validation_tests/{}/tolerance: must be 0 (approximation is forbidden)",
+                    i
+                ));
+            }
+            ids.push(id);
+        }
+
+        Ok(Requirements {
+            conformance: text(&doc, "/@conformance")?,
+            invariants: strings(&doc, "/required_invariants")?,
+            capabilities,
+            fallback_required: doc
+                .pointer("/kernel_requirements/cpu_fallback/required")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            fallback_mode: text(&doc, "/kernel_requirements/cpu_fallback/mode")?,
+            forbidden: strings(&doc, "/forbidden")?,
+            tests: ids,
+            pass: text(&doc, "/compliance_result/pass")?,
+            fail: text(&doc, "/compliance_result/fail")?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Certificate {
+    pub conformance: String,
+    pub engine: String,
+    pub results: Vec<TestResult>,
+    pub compliance: String,
+}
+
+impl Certificate {
+    /// The worst verdict across all results.
+    pub fn result(&self) -> Verdict {
+        self.results
+            .iter()
+            .map(|r| r.result)
+            .max()
+            .unwrap_or(Verdict::Pass)
+    }
+
+    pub fn render(&self) -> String {
+        let results = self
+            .results
+            .iter()
+            .map(|r| format!("    {}", r.object(Some(&r.hash()))))
+            .collect::<Vec<_>>()
+            .join(",\n");
+        format!(
+            "{{\n  \"@certificate\": \"{}\",\n  \"@conformance\": \"{}\",\n  \"engine\": \"{}\",\n  \"results\": [\n{}\n  ],\n  \"@result\": \"{}\",\n  \"compliance\": \"{}\"\n}}\n",
+            CERTIFICATE_TYPE,
+            self.conformance,
+            self.engine,
+            results,
+            self.result(),
+            self.compliance
+        )
+    }
+}
+
+fn lane(orientation: i8, mass_geometric: f64, mass_algebraic: f64) -> Vec<u8> {
+    PiSignal {
+        phase: PI / 3.0,
+        orientation,
+        curvature: 1.0,
+        mass_geometric,
+        mass_algebraic,
+        closed: true,
+        proof_offset: 0,
+    }
+    .encode()
+    .to_vec()
+}
+
+fn collapse_bits(run: &Run) -> Option<u64> {
+    run.value().map(f64::to_bits)
+}
+
+/// π + finite residue: the engine's series collapse minus the residue projects
+/// to π exactly, and a half-turn over (π, residue) collapses to the series.
+fn half_turn_closure(engine: &dyn Engine, profile: &str) -> Result<(), String> {
+    let series = engine.series(binary_split::terms_for_bits(64))?;
+    let residue = BigInt::from(binary_split::RESIDUE) * BigInt::from(series.denominator.clone());
+    let pi = exact::project(&(&series.numerator - residue), &series.denominator)?;
+    if pi.to_bits() != PI.to_bits() {
+        return Err(format!("series collapse minus residue is {:?}, not π", pi));
+    }
+    let run = engine.run(
+        profile,
+        &lane(1, pi, binary_split::RESIDUE as f64),
+        "π-gccp.half-turn.series.v1",
+    )?;
+    if run.quarter_turns != 2 || run.orientation_out != run.orientation_in {
+        return Err("half-turn must shift phase by π and preserve orientation".to_string());
+    }
+    if collapse_bits(&run) != Some(series.value()?.to_bits()) {
+        return Err("half-turn collapse differs from the series collapse".to_string());
+    }
+    Ok(())
+}
+
+/// Flipping orientation changes nothing but orientation.
+fn mobius_inversion(engine: &dyn Engine, profile: &str) -> Result<(), String> {
+    let up = engine.run(profile, &lane(1, PI, 3.0), "π-gccp.mobius.v1")?;
+    let down = engine.run(profile, &lane(-1, PI, 3.0), "π-gccp.mobius.v1")?;
+    for run in [&up, &down] {
+        if run.orientation_out != -run.orientation_in {
+            return Err("mobius must flip orientation".to_string());
+        }
+    }
+    if collapse_bits(&up).is_none() || collapse_bits(&up) != collapse_bits(&down) {
+        return Err("collapse differs under orientation flip".to_string());
+    }
+    if up.phase.to_bits() != down.phase.to_bits() || up.quarter_turns != down.quarter_turns {
+        return Err("phase differs under orientation flip".to_string());
+    }
+    Ok(())
+}
+
+/// Every profile, in either submission order, yields the same proofs and the
+/// same collapse ordering.
+fn profile_invariance(engine: &dyn Engine) -> Result<(), String> {
+    let lanes: Vec<Vec<u8>> = [(PI, 3.0), (1.5, -0.25), (PI, 1.0), (0.5, 0.5)]
+        .iter()
+        .map(|&(mg, ma)| lane(1, mg, ma))
+        .collect();
+    let mut reference: Option<Vec<String>> = None;
+    for profile in engine.profiles() {
+        for reversed in [false, true] {
+            let mut order: Vec<usize> = (0..lanes.len()).collect();
+            if reversed {
+                order.reverse();
+            }
+            let mut runs = Vec::new();
+            for i in order {
+                runs.push((
+                    i,
+                    engine.run(profile, &lanes[i], "π-gccp.half-turn.series.v1")?,
+                ));
+            }
+            runs.sort_by(|(ia, a), (ib, b)| {
+                a.value()
+                    .partial_cmp(&b.value())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(ia.cmp(ib))
+            });
+            let proofs: Vec<String> = runs.iter().map(|(_, run)| run.render()).collect();
+            match &reference {
+                None => reference = Some(proofs),
+                Some(expected) if *expected != proofs => {
+                    return Err(format!("profile {} orders or renders differently", profile))
+                }
+                Some(_) => {}
+            }
+        }
+    }
+    if reference.is_none() {
+        return Err("engine declares no profiles".to_string());
+    }
+    Ok(())
+}
+
+fn verdict(test: &str, outcome: Result<(), String>, on_error: Verdict) -> TestResult {
+    TestResult {
+        test: test.to_string(),
+        result: if outcome.is_ok() {
+            Verdict::Pass
+        } else {
+            on_error
+        },
+        detail: outcome.err(),
+    }
+}
+
+/// Runs every requirement and validation test against `engine`.
+pub fn certify(requirements: &Requirements, engine: &dyn Engine) -> Certificate {
+    let mut results = Vec::new();
+
+    let reported = engine.capabilities();
+    let forbidden: Vec<&String> = requirements
+        .forbidden
+        .iter()
+        .filter(|practice| {
+            reported
+                .iter()
+                .any(|(name, on)| *on && name.rsplit('.').next() == Some(practice.as_str()))
+        })
+        .collect();
+    let forbidden_outcome = match forbidden.first() {
+        Some(practice) => Err(format!("engine reports forbidden {}", practice)),
+        None => Ok(()),
+    };
+    results.push(verdict("forbidden", forbidden_outcome, Verdict::Illegal));
+
+    let missing: Vec<&str> = requirements
+        .capabilities
+        .iter()
+        .filter(|name| reported.get(*name) != Some(&true))
+        .map(String::as_str)
+        .collect();
+    let capability_outcome = if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("missing capabilities: {}", missing.join(", ")))
+    };
+    results.push(verdict("capabilities", capability_outcome, Verdict::Fail));
+
+    let supported = engine.invariants();
+    let absent: Vec<&str> = requirements
+        .invariants
+        .iter()
+        .filter(|id| !supported.contains(&id.as_str()))
+        .map(String::as_str)
+        .collect();
+    let invariant_outcome = if absent.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("missing invariants: {}", absent.join(", ")))
+    };
+    results.push(verdict(
+        "required-invariants",
+        invariant_outcome,
+        Verdict::Fail,
+    ));
+
+    let fallback = match engine.cpu_fallback() {
+        Some((_, true)) => verdict(
+            "cpu-fallback",
+            Err("fallback approximates".to_string()),
+            Verdict::Illegal,
+        ),
+        Some((mode, false)) if mode != requirements.fallback_mode => verdict(
+            "cpu-fallback",
+            Err(format!(
+                "fallback mode {} is not {}",
+                mode, requirements.fallback_mode
+            )),
+            Verdict::Fail,
+        ),
+        None if requirements.fallback_required => verdict(
+            "cpu-fallback",
+            Err("no CPU fallback".to_string()),
+            Verdict::Fail,
+        ),
+        _ => verdict("cpu-fallback", Ok(()), Verdict::Fail),
+    };
+    results.push(fallback);
+
+    let profile = engine
+        .profiles()
+        .first()
+        .copied()
+        .unwrap_or(REFERENCE_PROFILE);
+    for test in &requirements.tests {
+        let outcome = match test.as_str() {
+            "half-turn-closure" => half_turn_closure(engine, profile),
+            "mobius-inversion" => mobius_inversion(engine, profile),
+            "profile-invariance" => profile_invariance(engine),
+            other => Err(format!("no executable definition for {}", other)),
+        };
+        results.push(verdict(test, outcome, Verdict::Fail));
+    }
+
+    let mut certificate = Certificate {
+        conformance: requirements.conformance.clone(),
+        engine: engine.id().to_string(),
+        results,
+        compliance: String::new(),
+    };
+    certificate.compliance = if certificate.result() == Verdict::Pass {
+        requirements.pass.clone()
+    } else {
+        requirements.fail.clone()
+    };
+    certificate
+}
@@ -0,0 +1,183 @@
+// pi_determinism.rs
+// π-Adapter Determinism Check
+// Version: v1
+//
+// Determinism law of docs/pi-adapter-interface-v1.md: same input + same
+// adapter + same version → identical pi.signal.v1. Recorded outputs are
+// grouped by (source_hash, adapter, adapter_version) and compared in canonical
+// form, where every number is its f64 bit pattern; a group holding more than
+// one distinct output names the fields that differ.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde_json::Value;
+
+use crate::pi_adapter;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub source_hash: String,
+    pub adapter: String,
+    pub adapter_version: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Recording {
+    pub origin: String,
+    pub key: Key,
+    pub signal: Value,
+}
+
+/// Canonical form: JSON pointer → value, numbers as f64 bit patterns.
+pub type Canonical = BTreeMap<String, String>;
+
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    pub key: Key,
+    /// Origins of each distinct output, in first-seen order.
+    pub outputs: Vec<Vec<String>>,
+    pub fields: Vec<String>,
+}
+
+fn provenance_key(signal: &Value) -> Key {
+    let field = |name: &str| {
+        signal
+            .pointer(&format!("/provenance/{}", name))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    Key {
+        source_hash: field("source_hash"),
+        adapter: field("adapter"),
+        adapter_version: field("adapter_version"),
+    }
+}
+
+/// One adapter output file; the triple comes from its provenance.
+pub fn from_signal(bytes: &[u8], origin: &str) -> Result<Recording, String> {
+    let signal = pi_adapter::load(bytes).map_err(|e| format!("{} ({})", e, origin))?;
+    Ok(Recording {
+        origin: origin.to_string(),
+        key: provenance_key(&signal),
+        signal,
+    })
+}
+
+/// A recordings file: a JSON array of
+/// `{source_hash, adapter, adapter_version, output}`. Each output must be a
+/// valid signal whose provenance agrees with its recorded triple.
+pub fn from_recordings(bytes: &[u8], origin: &str) -> Result<Vec<Recording>, String> {
+    let doc: Value = serde_json::from_slice(bytes)
+        .map_err(|_| format!("recordings are not valid JSON ({})", origin))?;
+    let records = doc
+        .as_array()
+        .ok_or_else(|| format!("recordings must be an array ({})", origin))?;
+    let mut recordings = Vec::new();
+    for (i, record) in records.iter().enumerate() {
+        let here = format!("{}#/{}", origin, i);
+        let text = |name: &str| {
+            record
+                .get(name)
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| format!("/{}/{}: missing ({})", i, name, origin))
+        };
+        let key = Key {
+            source_hash: text("source_hash")?,
+            adapter: text("adapter")?,
+            adapter_version: text("adapter_version")?,
+        };
+        let signal = record
+            .get("output")
+            .ok_or_else(|| format!("/{}/output: missing ({})", i, origin))?;
+        pi_adapter::validate(signal).map_err(|e| format!("/{}/output{} ({})", i, e, origin))?;
+        if provenance_key(signal) != key {
+            return Err(format!(
+                "/{}/output/provenance: differs from the recorded triple ({})",
+                i, origin
+            ));
+        }
+        recordings.push(Recording {
+            origin: here,
+            key,
+            signal: signal.clone(),
+        });
+    }
+    Ok(recordings)
+}
+
+fn flatten(value: &Value, pointer: String, out: &mut Canonical) {
+    match value {
+        Value::Object(map) => {
+            out.insert(pointer.clone(), "{}".to_string());
+            for (key, item) in map {
+                flatten(item, format!("{}/{}", pointer, key), out);
+            }
+        }
+        Value::Array(items) => {
+            out.insert(pointer.clone(), format!("[{}]", items.len()));
+            for (i, item) in items.iter().enumerate() {
+                flatten(item, format!("{}/{}", pointer, i), out);
+            }
+        }
+        Value::Number(n) => {
+            let bits = n.as_f64().map(f64::to_bits).unwrap_or_default();
+            out.insert(pointer, format!("f64:0x{:016x}", bits));
+        }
+        other => {
+            out.insert(pointer, other.to_string());
+        }
+    }
+}
+
+pub fn canonical(signal: &Value) -> Canonical {
+    let mut out = BTreeMap::new();
+    flatten(signal, String::new(), &mut out);
+    out
+}
+
+/// Groups recordings by triple and reports every group with distinct outputs.
+pub fn check(recordings: &[Recording]) -> Vec<Divergence> {
+    let mut groups: BTreeMap<&Key, Vec<(Canonical, Vec<String>)>> = BTreeMap::new();
+    for recording in recordings {
+        let form = canonical(&recording.signal);
+        let variants = groups.entry(&recording.key).or_default();
+        match variants.iter_mut().find(|(seen, _)| *seen == form) {
+            Some((_, origins)) => origins.push(recording.origin.clone()),
+            None => variants.push((form, vec![recording.origin.clone()])),
+        }
+    }
+
+    let mut divergences = Vec::new();
+    for (key, variants) in groups {
+        if variants.len() < 2 {
+            continue;
+        }
+        let pointers: BTreeSet<&String> =
+            variants.iter().flat_map(|(form, _)| form.keys()).collect();
+        let fields = pointers
+            .into_iter()
+            .filter(|pointer| {
+                let first = variants[0].0.get(*pointer);
+                variants.iter().any(|(form, _)| form.get(*pointer) != first)
+            })
+            .map(|pointer| pointer.to_string())
+            .collect();
+        divergences.push(Divergence {
+            key: key.clone(),
+            outputs: variants.into_iter().map(|(_, origins)| origins).collect(),
+            fields,
+        });
+    }
+    divergences
+}
+
+/// Number of distinct triples among `recordings`.
+pub fn group_count(recordings: &[Recording]) -> usize {
+    recordings
+        .iter()
+        .map(|r| &r.key)
+        .collect::<BTreeSet<_>>()
+        .len()
+}
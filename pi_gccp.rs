@@ -0,0 +1,234 @@
+// pi_gccp.rs
+// π-GCCP Reference Engine
+// Version: v1
+//
+// The smallest lawful engine of docs/pi-gccp-reference-engine-v1.md, CPU only:
+// load a π-signal lane, verify its hash, normalize phase, apply one invariant,
+// collapse exactly and emit a proof for the run. Invariant phase shifts are
+// whole quarter turns (k·π/2) and are carried as integers, never as floats;
+// the collapse is the exact rational mass_geometric + mass_algebraic, projected
+// to f64 once. This engine is the ground truth accelerated engines match
+// bit for bit.
+
+use std::f64::consts::TAU;
+
+use crate::exact::Ratio;
+use crate::hash::Digest;
+use crate::pi_signal::{self, PiSignal, PiSignalView};
+
+pub const ENGINE: &str = "π-gccp.reference.v1";
+pub const PROOF_TYPE: &str = "π-gccp.run.v1";
+
+/// A π-GCCP invariant: how it moves phase and orientation, whether it closes,
+/// and which lanes it admits.
+pub trait Invariant {
+    fn id(&self) -> &'static str;
+
+    /// Short CLI name.
+    fn name(&self) -> &'static str;
+
+    /// Phase shift in quarter turns (π/2).
+    fn quarter_turns(&self) -> u8;
+
+    fn flips_orientation(&self) -> bool;
+
+    /// Whether a run under this invariant collapses to a scalar.
+    fn closes(&self) -> bool;
+
+    /// Lane conditions required before the invariant may apply.
+    fn admit(&self, signal: &PiSignal) -> Result<(), String>;
+}
+
+/// π-gccp.half-turn.series.v1: closure by half-turn, orientation preserved.
+pub struct HalfTurn;
+
+/// π-gccp.mobius.v1: phase shift π with an orientation flip on a single cycle.
+pub struct Mobius;
+
+/// π-gccp.saddle.v1: quarter-turn on negative curvature, never closed.
+pub struct Saddle;
+
+impl Invariant for HalfTurn {
+    fn id(&self) -> &'static str {
+        "π-gccp.half-turn.series.v1"
+    }
+
+    fn name(&self) -> &'static str {
+        "half-turn"
+    }
+
+    fn quarter_turns(&self) -> u8 {
+        2
+    }
+
+    fn flips_orientation(&self) -> bool {
+        false
+    }
+
+    fn closes(&self) -> bool {
+        true
+    }
+
+    fn admit(&self, signal: &PiSignal) -> Result<(), String> {
+        if !signal.closed {
+            return Err("half-turn requires a closed lane".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Invariant for Mobius {
+    fn id(&self) -> &'static str {
+        "π-gccp.mobius.v1"
+    }
+
+    fn name(&self) -> &'static str {
+        "mobius"
+    }
+
+    fn quarter_turns(&self) -> u8 {
+        2
+    }
+
+    fn flips_orientation(&self) -> bool {
+        true
+    }
+
+    fn closes(&self) -> bool {
+        true
+    }
+
+    fn admit(&self, signal: &PiSignal) -> Result<(), String> {
+        if !signal.closed {
+            return Err("mobius requires single-cycle path closure".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl Invariant for Saddle {
+    fn id(&self) -> &'static str {
+        "π-gccp.saddle.v1"
+    }
+
+    fn name(&self) -> &'static str {
+        "saddle"
+    }
+
+    fn quarter_turns(&self) -> u8 {
+        1
+    }
+
+    fn flips_orientation(&self) -> bool {
+        false
+    }
+
+    fn closes(&self) -> bool {
+        false
+    }
+
+    fn admit(&self, signal: &PiSignal) -> Result<(), String> {
+        if signal.curvature >= 0.0 {
+            return Err("saddle requires curvature < 0".to_string());
+        }
+        if signal.closed {
+            return Err("saddle requires closure_flag 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+pub const INVARIANTS: [&dyn Invariant; 3] = [&HalfTurn, &Mobius, &Saddle];
+
+/// Looks an invariant up by short name or full id.
+pub fn invariant(name: &str) -> Option<&'static dyn Invariant> {
+    INVARIANTS
+        .iter()
+        .copied()
+        .find(|inv| inv.name() == name || inv.id() == name)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub invariant: &'static str,
+    pub lane_hash: Digest,
+    /// Lane phase normalized into [0, 2π).
+    pub phase: f64,
+    /// Exact quarter turns added by the invariant, mod 4.
+    pub quarter_turns: u8,
+    pub orientation_in: i8,
+    pub orientation_out: i8,
+    pub closed: bool,
+    pub collapse: Option<Ratio>,
+}
+
+/// Runs the core loop on one encoded lane.
+pub fn run(lane: &[u8], invariant: &dyn Invariant) -> Result<Run, String> {
+    let view = PiSignalView::new(lane)?;
+    view.verify()?;
+    let signal = view.to_signal();
+    invariant.admit(&signal)?;
+
+    let phase = signal.phase.rem_euclid(TAU);
+    let orientation_out = if invariant.flips_orientation() {
+        -signal.orientation
+    } else {
+        signal.orientation
+    };
+    let collapse = if invariant.closes() {
+        let geometric = Ratio::from_f64(signal.mass_geometric)?;
+        let algebraic = Ratio::from_f64(signal.mass_algebraic)?;
+        let exact = geometric.add(&algebraic);
+        exact.to_f64()?;
+        Some(exact)
+    } else {
+        None
+    };
+
+    Ok(Run {
+        invariant: invariant.id(),
+        lane_hash: view.lane_hash(),
+        phase,
+        quarter_turns: invariant.quarter_turns() % 4,
+        orientation_in: signal.orientation,
+        orientation_out,
+        closed: signal.closed,
+        collapse,
+    })
+}
+
+impl Run {
+    /// The collapse projected to f64, if the invariant closes.
+    pub fn value(&self) -> Option<f64> {
+        self.collapse
+            .as_ref()
+            .map(|exact| exact.to_f64().expect("checked when the run was made"))
+    }
+
+    /// Canonical proof text. The same lane and invariant yield identical bytes.
+    pub fn render(&self) -> String {
+        let collapse = match (&self.collapse, self.value()) {
+            (Some(exact), Some(value)) => format!(
+                "{{\"exact\":\"{}\",\"bits\":\"0x{:016x}\",\"decimal\":\"{:?}\"}}",
+                exact,
+                value.to_bits(),
+                value
+            ),
+            _ => "null".to_string(),
+        };
+        format!(
+            "{{\n  \"@proof\": \"{}\",\n  \"engine\": \"{}\",\n  \"invariant\": \"{}\",\n  \"input\": {{\"lane\":\"{}\",\"hash\":\"{}\"}},\n  \"phase\": {{\"normalized\":\"0x{:016x}\",\"quarter_turns\":{}}},\n  \"orientation\": {{\"in\":{},\"out\":{}}},\n  \"closure\": {},\n  \"collapse\": {},\n  \"approximation\": false\n}}\n",
+            PROOF_TYPE,
+            ENGINE,
+            self.invariant,
+            pi_signal::LANE_ID,
+            self.lane_hash,
+            self.phase.to_bits(),
+            self.quarter_turns,
+            self.orientation_in,
+            self.orientation_out,
+            self.closed,
+            collapse
+        )
+    }
+}
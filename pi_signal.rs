@@ -0,0 +1,307 @@
+// pi_signal.rs
+// SCXQ2 π-Signal Lane (scxq2.pi-signal.v1)
+// Version: v1
+//
+// Binary execution lane from docs/scxq2-pi-signal-lane-v1.md. Little-endian,
+// every field on an 8-byte boundary, padding bytes zero:
+//
+//   0  magic u32 (0x50534947)   4  version u16   6  flags u16
+//   8  phase f64               16  orientation i8 (+7 pad)
+//  24  curvature f64           32  mass_geometric f64
+//  40  mass_algebraic f64      48  closure_flag u8 (+7 pad)
+//  56  blake3 footer [32] over bytes 0..56
+//  88  proof_offset u32 (+4 pad)                               = 96 bytes
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::hash::{Algorithm, Digest};
+
+pub const LANE_ID: &str = "scxq2.pi-signal.v1";
+pub const LANE_EXTENSION: &str = "pisig";
+pub const LANE_SUFFIX: &str = ".pisig";
+pub const MAGIC: u32 = 0x5053_4947;
+pub const VERSION: u16 = 1;
+pub const ALIGNMENT: usize = 8;
+pub const LANE_SIZE: usize = 96;
+pub const FOOTER_HASH: Algorithm = Algorithm::Blake3;
+
+pub const FLAG_EXACT_MATH: u16 = 1 << 0;
+pub const FLAG_ORIENTATION_SENSITIVE: u16 = 1 << 1;
+pub const FLAG_PROFILE_INDEPENDENT: u16 = 1 << 2;
+pub const FLAGS: u16 = FLAG_EXACT_MATH | FLAG_ORIENTATION_SENSITIVE | FLAG_PROFILE_INDEPENDENT;
+
+const OFF_MAGIC: usize = 0;
+const OFF_VERSION: usize = 4;
+const OFF_FLAGS: usize = 6;
+const OFF_PHASE: usize = 8;
+const OFF_ORIENTATION: usize = 16;
+const OFF_CURVATURE: usize = 24;
+const OFF_MASS_GEOMETRIC: usize = 32;
+const OFF_MASS_ALGEBRAIC: usize = 40;
+const OFF_CLOSURE: usize = 48;
+const OFF_FOOTER_HASH: usize = 56;
+const OFF_PROOF_OFFSET: usize = 88;
+
+const PADDING: [(usize, usize); 3] = [(17, 24), (49, 56), (92, 96)];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PiSignal {
+    pub phase: f64,
+    pub orientation: i8,
+    pub curvature: f64,
+    pub mass_geometric: f64,
+    pub mass_algebraic: f64,
+    pub closed: bool,
+    pub proof_offset: u32,
+}
+
+/// Encoded lanes are `.pisig` files: aligned binary fields, not CM-1 text.
+pub fn is_lane(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some(LANE_EXTENSION)
+}
+
+/// Borrowed view over one encoded lane; fields are read in place.
+#[derive(Debug, Clone, Copy)]
+pub struct PiSignalView<'a> {
+    bytes: &'a [u8],
+}
+
+/// collapse(π-signal): closed lanes yield mass_geometric + mass_algebraic,
+/// open lanes are illegal.
+pub fn collapse_rule(
+    closed: bool,
+    mass_geometric: f64,
+    mass_algebraic: f64,
+) -> Result<f64, String> {
+    if !closed {
+        return Err("collapse of an open π-signal lane is illegal".to_string());
+    }
+    Ok(mass_geometric + mass_algebraic)
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([bytes[offset], bytes[offset + 1]])
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    let mut raw = [0u8; 4];
+    raw.copy_from_slice(&bytes[offset..offset + 4]);
+    u32::from_le_bytes(raw)
+}
+
+fn f64_at(bytes: &[u8], offset: usize) -> f64 {
+    let mut raw = [0u8; 8];
+    raw.copy_from_slice(&bytes[offset..offset + 8]);
+    f64::from_le_bytes(raw)
+}
+
+impl<'a> PiSignalView<'a> {
+    /// Borrows `bytes` as a lane. Only the size is checked here; see `verify`.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, String> {
+        if bytes.len() != LANE_SIZE {
+            return Err(format!(
+                "π-signal lane must be {} bytes, got {}",
+                LANE_SIZE,
+                bytes.len()
+            ));
+        }
+        Ok(PiSignalView { bytes })
+    }
+
+    pub fn magic(&self) -> u32 {
+        u32_at(self.bytes, OFF_MAGIC)
+    }
+
+    pub fn version(&self) -> u16 {
+        u16_at(self.bytes, OFF_VERSION)
+    }
+
+    pub fn flags(&self) -> u16 {
+        u16_at(self.bytes, OFF_FLAGS)
+    }
+
+    pub fn phase(&self) -> f64 {
+        f64_at(self.bytes, OFF_PHASE)
+    }
+
+    pub fn orientation(&self) -> i8 {
+        self.bytes[OFF_ORIENTATION] as i8
+    }
+
+    pub fn curvature(&self) -> f64 {
+        f64_at(self.bytes, OFF_CURVATURE)
+    }
+
+    pub fn mass_geometric(&self) -> f64 {
+        f64_at(self.bytes, OFF_MASS_GEOMETRIC)
+    }
+
+    pub fn mass_algebraic(&self) -> f64 {
+        f64_at(self.bytes, OFF_MASS_ALGEBRAIC)
+    }
+
+    pub fn closure_flag(&self) -> u8 {
+        self.bytes[OFF_CLOSURE]
+    }
+
+    pub fn footer_hash(&self) -> &'a [u8] {
+        &self.bytes[OFF_FOOTER_HASH..OFF_FOOTER_HASH + 32]
+    }
+
+    pub fn proof_offset(&self) -> u32 {
+        u32_at(self.bytes, OFF_PROOF_OFFSET)
+    }
+
+    /// Magic, version, flags, alignment (zero padding, 8-aligned proof offset),
+    /// orientation ∈ {+1, −1}, closure flag, finite fields and the footer hash.
+    pub fn verify(&self) -> Result<(), String> {
+        if self.magic() != MAGIC {
+            return Err(format!(
+                "magic 0x{:08X} is not 0x{:08X}",
+                self.magic(),
+                MAGIC
+            ));
+        }
+        if self.version() != VERSION {
+            return Err(format!("unsupported version {}", self.version()));
+        }
+        if self.flags() != FLAGS {
+            return Err(format!(
+                "flags 0x{:04X} must be 0x{:04X}",
+                self.flags(),
+                FLAGS
+            ));
+        }
+        for (start, end) in PADDING {
+            if self.bytes[start..end].iter().any(|b| *b != 0) {
+                return Err(format!(
+                    "alignment padding at {}..{} is not zero",
+                    start, end
+                ));
+            }
+        }
+        if !(self.proof_offset() as usize).is_multiple_of(ALIGNMENT) {
+            return Err(format!(
+                "proof_offset {} is not {}-byte aligned",
+                self.proof_offset(),
+                ALIGNMENT
+            ));
+        }
+        if !matches!(self.orientation(), 1 | -1) {
+            return Err(format!(
+                "orientation {} must be +1 or -1",
+                self.orientation()
+            ));
+        }
+        if self.closure_flag() > 1 {
+            return Err(format!(
+                "closure_flag {} must be 0 or 1",
+                self.closure_flag()
+            ));
+        }
+        let fields = [
+            ("phase", self.phase()),
+            ("curvature", self.curvature()),
+            ("mass_geometric", self.mass_geometric()),
+            ("mass_algebraic", self.mass_algebraic()),
+        ];
+        for (name, value) in fields {
+            if !value.is_finite() {
+                return Err(format!("{} is not finite", name));
+            }
+        }
+        let expected = Digest::of(FOOTER_HASH, &self.bytes[..OFF_FOOTER_HASH]);
+        if self.footer_hash() != expected.bytes {
+            return Err("footer hash does not match lane bytes".to_string());
+        }
+        Ok(())
+    }
+
+    /// The lane's digest as referenced by proofs (blake3 over all 96 bytes).
+    pub fn lane_hash(&self) -> Digest {
+        Digest::of(FOOTER_HASH, self.bytes)
+    }
+
+    pub fn to_signal(self) -> PiSignal {
+        PiSignal {
+            phase: self.phase(),
+            orientation: self.orientation(),
+            curvature: self.curvature(),
+            mass_geometric: self.mass_geometric(),
+            mass_algebraic: self.mass_algebraic(),
+            closed: self.closure_flag() == 1,
+            proof_offset: self.proof_offset(),
+        }
+    }
+
+    pub fn collapse(&self) -> Result<f64, String> {
+        collapse_rule(
+            self.closure_flag() == 1,
+            self.mass_geometric(),
+            self.mass_algebraic(),
+        )
+    }
+}
+
+fn number(doc: &Value, key: &str) -> Result<f64, String> {
+    doc.get(key)
+        .and_then(Value::as_f64)
+        .ok_or_else(|| format!("π-signal: missing {}", key))
+}
+
+impl PiSignal {
+    /// Reads the field values from a JSON object keyed by the spec's field names.
+    pub fn from_json(bytes: &[u8]) -> Result<PiSignal, String> {
+        let doc: Value =
+            serde_json::from_slice(bytes).map_err(|_| "π-signal is not valid JSON".to_string())?;
+        let orientation = doc
+            .get("orientation")
+            .and_then(Value::as_i64)
+            .filter(|o| matches!(o, 1 | -1))
+            .ok_or_else(|| "π-signal: orientation must be +1 or -1".to_string())?;
+        let closed = match doc.get("closure_flag").and_then(Value::as_u64) {
+            Some(0) => false,
+            Some(1) => true,
+            _ => return Err("π-signal: closure_flag must be 0 or 1".to_string()),
+        };
+        let proof_offset = match doc.get("proof_offset") {
+            None => 0,
+            Some(value) => value
+                .as_u64()
+                .and_then(|o| u32::try_from(o).ok())
+                .ok_or_else(|| "π-signal: proof_offset is not a u32".to_string())?,
+        };
+        Ok(PiSignal {
+            phase: number(&doc, "phase")?,
+            orientation: orientation as i8,
+            curvature: number(&doc, "curvature")?,
+            mass_geometric: number(&doc, "mass_geometric")?,
+            mass_algebraic: number(&doc, "mass_algebraic")?,
+            closed,
+            proof_offset,
+        })
+    }
+
+    /// Serializes the lane and seals it with the blake3 footer.
+    pub fn encode(&self) -> [u8; LANE_SIZE] {
+        let mut out = [0u8; LANE_SIZE];
+        out[OFF_MAGIC..OFF_MAGIC + 4].copy_from_slice(&MAGIC.to_le_bytes());
+        out[OFF_VERSION..OFF_VERSION + 2].copy_from_slice(&VERSION.to_le_bytes());
+        out[OFF_FLAGS..OFF_FLAGS + 2].copy_from_slice(&FLAGS.to_le_bytes());
+        out[OFF_PHASE..OFF_PHASE + 8].copy_from_slice(&self.phase.to_le_bytes());
+        out[OFF_ORIENTATION] = self.orientation as u8;
+        out[OFF_CURVATURE..OFF_CURVATURE + 8].copy_from_slice(&self.curvature.to_le_bytes());
+        out[OFF_MASS_GEOMETRIC..OFF_MASS_GEOMETRIC + 8]
+            .copy_from_slice(&self.mass_geometric.to_le_bytes());
+        out[OFF_MASS_ALGEBRAIC..OFF_MASS_ALGEBRAIC + 8]
+            .copy_from_slice(&self.mass_algebraic.to_le_bytes());
+        out[OFF_CLOSURE] = u8::from(self.closed);
+        let footer = Digest::of(FOOTER_HASH, &out[..OFF_FOOTER_HASH]);
+        out[OFF_FOOTER_HASH..OFF_FOOTER_HASH + 32].copy_from_slice(&footer.bytes);
+        out[OFF_PROOF_OFFSET..OFF_PROOF_OFFSET + 4]
+            .copy_from_slice(&self.proof_offset.to_le_bytes());
+        out
+    }
+}
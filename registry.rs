@@ -0,0 +1,302 @@
+// registry.rs
+// SMCA Registry Resolver
+// Version: v1
+//
+// Loads registry/manifest.json and cross-links the collapse geometry index,
+// the kernel directory and the CM-1 profile table. Resolution is read-only.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::hash::{Algorithm, Digest};
+
+pub const MANIFEST_FILE: &str = "manifest.json";
+pub const REGISTRY_DIR: &str = "registry";
+
+// Geometry versions are pinned by blake3 (registry/collapse-geometry/<version>.hash).
+pub const GEOMETRY_HASH: Algorithm = Algorithm::Blake3;
+
+#[derive(Debug, Clone)]
+pub struct Kernel {
+    pub id: String,
+    pub path: PathBuf,
+    pub cm1_profile: Option<String>,
+    pub collapse_geometry: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GeometryDoc {
+    pub version: String,
+    pub path: PathBuf,
+    pub hash: Digest,
+    pub doc: Value,
+}
+
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub path: PathBuf,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub struct Registry {
+    pub root: PathBuf,
+    pub geometry_latest: String,
+    pub geometry_versions: BTreeMap<String, String>,
+    pub kernels: BTreeMap<String, Kernel>,
+    pub profiles: BTreeMap<String, Value>,
+    geometry_index_path: PathBuf,
+    profiles_path: PathBuf,
+    issues: Vec<Issue>,
+}
+
+fn read_json(path: &Path) -> Result<Value, String> {
+    let bytes = fs::read(path).map_err(|_| format!("cannot read {}", path.display()))?;
+    serde_json::from_slice(&bytes)
+        .map_err(|e| format!("invalid JSON in {} ({})", path.display(), e))
+}
+
+fn str_field<'a>(doc: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter()
+        .find_map(|key| doc.get(*key).and_then(Value::as_str))
+}
+
+/// Finds the nearest `registry/` holding a manifest, walking up from `start`.
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    let start = fs::canonicalize(start).ok()?;
+    let start = if start.is_dir() {
+        start.as_path()
+    } else {
+        start.parent()?
+    };
+    start
+        .ancestors()
+        .map(|dir| dir.join(REGISTRY_DIR))
+        .find(|candidate| candidate.join(MANIFEST_FILE).is_file())
+}
+
+impl Registry {
+    /// Loads the registry rooted at `root` (the directory holding manifest.json).
+    /// Structural errors abort the load; dangling references are collected as issues.
+    pub fn load(root: &Path) -> Result<Registry, String> {
+        let manifest = read_json(&root.join(MANIFEST_FILE))?;
+        if manifest.get("@registry").and_then(Value::as_str).is_none() {
+            return Err("registry manifest missing @registry".to_string());
+        }
+        let entries = manifest
+            .get("entries")
+            .and_then(Value::as_object)
+            .ok_or_else(|| "registry manifest missing entries".to_string())?;
+        let entry = |name: &str| {
+            entries
+                .get(name)
+                .and_then(Value::as_str)
+                .map(|rel| root.join(rel))
+                .ok_or_else(|| format!("registry manifest missing entries.{}", name))
+        };
+
+        let geometry_index_path = entry("collapse_geometry")?;
+        let kernels_dir = entry("kernels")?;
+        let profiles_path = entry("cm1_profiles")?;
+
+        let mut issues = Vec::new();
+
+        let index = read_json(&geometry_index_path)?;
+        let geometry_latest = index
+            .get("latest")
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("missing latest ({})", geometry_index_path.display()))?
+            .to_string();
+        let geometry_versions: BTreeMap<String, String> = index
+            .get("versions")
+            .and_then(Value::as_object)
+            .ok_or_else(|| format!("missing versions ({})", geometry_index_path.display()))?
+            .iter()
+            .filter_map(|(k, v)| v.as_str().map(|file| (k.clone(), file.to_string())))
+            .collect();
+
+        let profiles_doc = read_json(&profiles_path)?;
+        let profiles: BTreeMap<String, Value> = profiles_doc
+            .get("profiles")
+            .and_then(Value::as_object)
+            .ok_or_else(|| format!("missing profiles ({})", profiles_path.display()))?
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+
+        let mut kernel_files: Vec<PathBuf> = fs::read_dir(&kernels_dir)
+            .map_err(|_| format!("cannot read {}", kernels_dir.display()))?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("json"))
+            .collect();
+        kernel_files.sort();
+
+        let mut kernels = BTreeMap::new();
+        for path in kernel_files {
+            let doc = read_json(&path)?;
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("")
+                .to_string();
+            let id = match str_field(&doc, &["@id", "@kernel"]) {
+                Some(id) => id.to_string(),
+                None => {
+                    issues.push(Issue {
+                        path: path.clone(),
+                        message: "kernel has no @id or @kernel".to_string(),
+                    });
+                    stem.clone()
+                }
+            };
+            if id != stem {
+                issues.push(Issue {
+                    path: path.clone(),
+                    message: format!("kernel id {} does not match file name", id),
+                });
+            }
+            if kernels.contains_key(&id) {
+                issues.push(Issue {
+                    path: path.clone(),
+                    message: format!("duplicate kernel id {}", id),
+                });
+                continue;
+            }
+            let kernel = Kernel {
+                cm1_profile: str_field(&doc, &["cm1_profile", "@cm1_profile"]).map(str::to_string),
+                collapse_geometry: str_field(&doc, &["@collapse_geometry", "collapse_geometry"])
+                    .map(str::to_string),
+                id: id.clone(),
+                path,
+            };
+            kernels.insert(id, kernel);
+        }
+
+        Ok(Registry {
+            root: root.to_path_buf(),
+            geometry_latest,
+            geometry_versions,
+            kernels,
+            profiles,
+            geometry_index_path,
+            profiles_path,
+            issues,
+        })
+    }
+
+    /// Directory that profile-relative paths (dictionary, lane_schema) are resolved against.
+    pub fn project_root(&self) -> &Path {
+        self.root.parent().unwrap_or(Path::new("."))
+    }
+
+    pub fn kernel(&self, id: &str) -> Option<&Kernel> {
+        self.kernels.get(id)
+    }
+
+    pub fn profile(&self, name: &str) -> Option<&Value> {
+        self.profiles.get(name)
+    }
+
+    /// Resolves a kernel id to its CM-1 profile.
+    pub fn kernel_profile(&self, id: &str) -> Result<(&str, &Value), String> {
+        let kernel = self
+            .kernel(id)
+            .ok_or_else(|| format!("unknown kernel {}", id))?;
+        let name = kernel
+            .cm1_profile
+            .as_deref()
+            .ok_or_else(|| format!("kernel {} declares no cm1_profile", id))?;
+        let profile = self
+            .profile(name)
+            .ok_or_else(|| format!("kernel {} names unknown cm1_profile {}", id, name))?;
+        Ok((name, profile))
+    }
+
+    /// Resolves a geometry version (or `latest` when `None`) to its document,
+    /// checking the bytes against the pinned `<version>.hash` file.
+    pub fn geometry(&self, version: Option<&str>) -> Result<GeometryDoc, String> {
+        let version = version.unwrap_or(&self.geometry_latest);
+        let file = self
+            .geometry_versions
+            .get(version)
+            .ok_or_else(|| format!("unknown collapse geometry version {}", version))?;
+        let dir = self.geometry_index_path.parent().unwrap_or(Path::new("."));
+        let path = dir.join(file);
+        let bytes = fs::read(&path).map_err(|_| format!("cannot read {}", path.display()))?;
+        let actual = Digest::of(GEOMETRY_HASH, &bytes);
+
+        let hash_path = dir.join(format!("{}.hash", version));
+        let pinned = fs::read_to_string(&hash_path)
+            .map_err(|_| format!("missing pinned hash {}", hash_path.display()))?;
+        let pinned = Digest::parse(pinned.trim())
+            .map_err(|reason| format!("hash not pinned: {} ({})", reason, hash_path.display()))?;
+        pinned
+            .expect(GEOMETRY_HASH)
+            .map_err(|reason| format!("{} ({})", reason, hash_path.display()))?;
+        if pinned != actual {
+            return Err(format!("geometry hash mismatch ({})", path.display()));
+        }
+
+        let doc = serde_json::from_slice(&bytes)
+            .map_err(|e| format!("invalid JSON in {} ({})", path.display(), e))?;
+        Ok(GeometryDoc {
+            version: version.to_string(),
+            path,
+            hash: actual,
+            doc,
+        })
+    }
+
+    /// Every reference that does not resolve, in deterministic order.
+    pub fn dangling(&self) -> Vec<Issue> {
+        let mut issues = self.issues.clone();
+
+        if !self.geometry_versions.contains_key(&self.geometry_latest) {
+            issues.push(Issue {
+                path: self.geometry_index_path.clone(),
+                message: format!("latest names unknown version {}", self.geometry_latest),
+            });
+        }
+        for version in self.geometry_versions.keys() {
+            if let Err(reason) = self.geometry(Some(version)) {
+                issues.push(Issue {
+                    path: self.geometry_index_path.clone(),
+                    message: format!("collapse geometry {}: {}", version, reason),
+                });
+            }
+        }
+
+        for kernel in self.kernels.values() {
+            match kernel.cm1_profile.as_deref() {
+                None => issues.push(Issue {
+                    path: kernel.path.clone(),
+                    message: "kernel declares no cm1_profile".to_string(),
+                }),
+                Some(name) if !self.profiles.contains_key(name) => issues.push(Issue {
+                    path: kernel.path.clone(),
+                    message: format!("unknown cm1_profile {}", name),
+                }),
+                Some(_) => {}
+            }
+        }
+
+        for (name, profile) in &self.profiles {
+            for key in ["dictionary", "lane_schema"] {
+                if let Some(rel) = profile.get(key).and_then(Value::as_str) {
+                    if !self.project_root().join(rel).is_file() {
+                        issues.push(Issue {
+                            path: self.profiles_path.clone(),
+                            message: format!("profile {} {} not found ({})", name, key, rel),
+                        });
+                    }
+                }
+            }
+        }
+
+        issues
+    }
+}
@@ -0,0 +1,407 @@
+// scxq2_aggregate.rs
+// SCXQ2 Proof Aggregation (scxq2.aggregate.v1)
+// Version: v1
+//
+// Order-sensitive merkle fold over collapse proof hashes, per
+// docs/scxq2-proof-aggregation-v1.md. Leaf nodes are blake3(0x00 || leaf),
+// inner nodes blake3(0x01 || left || right); an odd node at the end of a level
+// is promoted unchanged. Inclusion paths hold one sibling per level.
+//
+// Every input is a collapse receipt paired with its π-signal lane. Building
+// or verifying an aggregate replays each receipt on its lane (pi_collapse.rs)
+// before it becomes a leaf; the replay is what backs the all_inputs_verified
+// and all_collapses_exact claims.
+
+use serde_json::Value;
+
+use crate::hash::{Algorithm, Digest, Hasher};
+use crate::pi_collapse::Receipt;
+
+pub const AGGREGATE_PROOF: &str = "scxq2.aggregate.v1";
+pub const INCLUSION_PROOF: &str = "scxq2.aggregate.inclusion.v1";
+pub const LEAF_TYPE: &str = crate::pi_collapse::PROOF_TYPE;
+pub const ROOT_HASH: Algorithm = Algorithm::Blake3;
+pub const VERIFICATION_STEPS: [&str; 3] = [
+    "verify_each_leaf",
+    "recompute_merkle_root",
+    "compare_root_hash",
+];
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaf {
+    pub proof_type: String,
+    pub hash: Digest,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Aggregate {
+    pub leaves: Vec<Leaf>,
+    pub root: Digest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inclusion {
+    pub root: Digest,
+    pub count: usize,
+    pub index: usize,
+    pub leaf: Leaf,
+    pub path: Vec<(Side, Digest)>,
+}
+
+fn leaf_node(leaf: &Leaf) -> Digest {
+    let mut hasher = Hasher::new(ROOT_HASH);
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(&leaf.hash.bytes);
+    hasher.finalize()
+}
+
+fn inner_node(left: &Digest, right: &Digest) -> Digest {
+    let mut hasher = Hasher::new(ROOT_HASH);
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(&left.bytes);
+    hasher.update(&right.bytes);
+    hasher.finalize()
+}
+
+fn fold_level(level: &[Digest]) -> Vec<Digest> {
+    level
+        .chunks(2)
+        .map(|pair| match pair {
+            [left, right] => inner_node(left, right),
+            [odd] => odd.clone(),
+            _ => unreachable!("chunks(2) yields one or two nodes"),
+        })
+        .collect()
+}
+
+/// Reads one leaf proof document: its `@proof` type and the blake3 of its bytes.
+pub fn leaf_from_proof(bytes: &[u8]) -> Result<Leaf, String> {
+    let doc: Value =
+        serde_json::from_slice(bytes).map_err(|_| "leaf proof is not valid JSON".to_string())?;
+    let proof_type = doc
+        .get("@proof")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "leaf proof missing @proof".to_string())?;
+    if proof_type != LEAF_TYPE {
+        return Err(format!(
+            "leaf proof type {} where {} is required",
+            proof_type, LEAF_TYPE
+        ));
+    }
+    Ok(Leaf {
+        proof_type: proof_type.to_string(),
+        hash: Digest::of(ROOT_HASH, bytes),
+    })
+}
+
+/// verify_each_leaf for one input: the receipt must replay bit-exactly on its
+/// lane before it counts as a leaf.
+pub fn verified_leaf(receipt: &[u8], lane: &[u8]) -> Result<Leaf, String> {
+    let leaf = leaf_from_proof(receipt)?;
+    Receipt::parse(receipt)?.replay(lane)?;
+    Ok(leaf)
+}
+
+pub fn merkle_root(leaves: &[Leaf]) -> Result<Digest, String> {
+    if leaves.is_empty() {
+        return Err("aggregate needs at least one leaf".to_string());
+    }
+    let mut level: Vec<Digest> = leaves.iter().map(leaf_node).collect();
+    while level.len() > 1 {
+        level = fold_level(&level);
+    }
+    Ok(level.remove(0))
+}
+
+fn digest_field(doc: &Value, path: &str) -> Result<Digest, String> {
+    let text = doc
+        .pointer(path)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("{} missing", path))?;
+    let digest = Digest::parse(text).map_err(|e| format!("{}: {}", path, e))?;
+    digest
+        .expect(ROOT_HASH)
+        .map_err(|e| format!("{}: {}", path, e))?;
+    Ok(digest)
+}
+
+fn parse_leaf(doc: &Value, path: &str) -> Result<Leaf, String> {
+    let proof_type = doc
+        .pointer(&format!("{}/type", path))
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("{}/type missing", path))?;
+    if proof_type != LEAF_TYPE {
+        return Err(format!(
+            "{}/type: {} is not {}",
+            path, proof_type, LEAF_TYPE
+        ));
+    }
+    Ok(Leaf {
+        proof_type: proof_type.to_string(),
+        hash: digest_field(doc, &format!("{}/hash", path))?,
+    })
+}
+
+impl Aggregate {
+    /// `inputs` are (receipt, lane) pairs in aggregate order.
+    pub fn build(inputs: &[(Vec<u8>, Vec<u8>)]) -> Result<Aggregate, String> {
+        let leaves = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, (receipt, lane))| {
+                verified_leaf(receipt, lane).map_err(|e| format!("verify_each_leaf[{}]: {}", i, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let root = merkle_root(&leaves)?;
+        Ok(Aggregate { leaves, root })
+    }
+
+    /// Canonical aggregate document, one leaf per line.
+    pub fn render(&self) -> String {
+        let proofs = self
+            .leaves
+            .iter()
+            .map(|leaf| {
+                format!(
+                    "      {{\"type\": \"{}\", \"hash\": \"{}\"}}",
+                    leaf.proof_type, leaf.hash
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        let steps = VERIFICATION_STEPS
+            .iter()
+            .map(|s| format!("\"{}\"", s))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "{{\n  \"@proof\": \"{}\",\n  \"@type\": \"batch-execution-proof\",\n  \"@verifiability\": \"deterministic\",\n  \"inputs\": {{\n    \"count\": {},\n    \"proofs\": [\n{}\n    ]\n  }},\n  \"aggregation\": {{\n    \"method\": \"merkle-fold\",\n    \"order_sensitive\": true,\n    \"root_hash\": \"{}\"\n  }},\n  \"claims\": {{\n    \"all_inputs_verified\": true,\n    \"all_collapses_exact\": true,\n    \"no_approximation\": true\n  }},\n  \"verification\": {{\n    \"steps\": [{}],\n    \"tolerance\": 0\n  }}\n}}\n",
+            AGGREGATE_PROOF,
+            self.leaves.len(),
+            proofs,
+            self.root,
+            steps
+        )
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Aggregate, String> {
+        let doc: Value =
+            serde_json::from_slice(bytes).map_err(|_| "aggregate is not valid JSON".to_string())?;
+        if doc.get("@proof").and_then(Value::as_str) != Some(AGGREGATE_PROOF) {
+            return Err(format!("@proof must be {}", AGGREGATE_PROOF));
+        }
+        if doc.pointer("/aggregation/method").and_then(Value::as_str) != Some("merkle-fold") {
+            return Err("/aggregation/method must be merkle-fold".to_string());
+        }
+        if doc
+            .pointer("/aggregation/order_sensitive")
+            .and_then(Value::as_bool)
+            != Some(true)
+        {
+            return Err("/aggregation/order_sensitive must be true".to_string());
+        }
+        if doc
+            .pointer("/verification/tolerance")
+            .and_then(Value::as_u64)
+            != Some(0)
+        {
+            return Err("/verification/tolerance must be 0".to_string());
+        }
+        let count = doc
+            .pointer("/inputs/count")
+            .and_then(Value::as_u64)
+            .filter(|c| *c <= u32::MAX as u64)
+            .ok_or_else(|| "/inputs/count must be u32".to_string())?;
+        let proofs = doc
+            .pointer("/inputs/proofs")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "/inputs/proofs missing".to_string())?;
+        if proofs.len() as u64 != count {
+            return Err("/inputs/count does not match /inputs/proofs".to_string());
+        }
+        let leaves = (0..proofs.len())
+            .map(|i| parse_leaf(&doc, &format!("/inputs/proofs/{}", i)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let root = digest_field(&doc, "/aggregation/root_hash")?;
+        Ok(Aggregate { leaves, root })
+    }
+
+    /// verify_each_leaf → recompute_merkle_root → compare_root_hash.
+    /// `inputs` are the (receipt, lane) pairs, in aggregate order.
+    pub fn verify(&self, inputs: &[(Vec<u8>, Vec<u8>)]) -> Result<(), String> {
+        if inputs.len() != self.leaves.len() {
+            return Err(format!(
+                "verify_each_leaf: {} inputs supplied for {} leaves",
+                inputs.len(),
+                self.leaves.len()
+            ));
+        }
+        for (i, ((receipt, lane), leaf)) in inputs.iter().zip(&self.leaves).enumerate() {
+            let actual = verified_leaf(receipt, lane)
+                .map_err(|e| format!("verify_each_leaf[{}]: {}", i, e))?;
+            if actual != *leaf {
+                return Err(format!("verify_each_leaf[{}]: leaf hash mismatch", i));
+            }
+        }
+        self.verify_root()
+    }
+
+    /// recompute_merkle_root → compare_root_hash over the listed leaf hashes.
+    pub fn verify_root(&self) -> Result<(), String> {
+        let root =
+            merkle_root(&self.leaves).map_err(|e| format!("recompute_merkle_root: {}", e))?;
+        if root != self.root {
+            return Err("compare_root_hash: root hash mismatch".to_string());
+        }
+        Ok(())
+    }
+
+    /// Sibling path from leaf `index` to the root: log2(N) entries at most.
+    pub fn inclusion(&self, index: usize) -> Result<Inclusion, String> {
+        let leaf = self
+            .leaves
+            .get(index)
+            .cloned()
+            .ok_or_else(|| format!("leaf index {} out of range", index))?;
+        let mut level: Vec<Digest> = self.leaves.iter().map(leaf_node).collect();
+        let mut position = index;
+        let mut path = Vec::new();
+        while level.len() > 1 {
+            let sibling = position ^ 1;
+            if sibling < level.len() {
+                let side = if sibling < position {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+                path.push((side, level[sibling].clone()));
+            }
+            level = fold_level(&level);
+            position /= 2;
+        }
+        Ok(Inclusion {
+            root: self.root.clone(),
+            count: self.leaves.len(),
+            index,
+            leaf,
+            path,
+        })
+    }
+}
+
+impl Inclusion {
+    pub fn render(&self) -> String {
+        let path = self
+            .path
+            .iter()
+            .map(|(side, hash)| {
+                let side = match side {
+                    Side::Left => "left",
+                    Side::Right => "right",
+                };
+                format!("    {{\"side\": \"{}\", \"hash\": \"{}\"}}", side, hash)
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        let path = if path.is_empty() {
+            String::new()
+        } else {
+            format!("\n{}\n  ", path)
+        };
+        format!(
+            "{{\n  \"@proof\": \"{}\",\n  \"root_hash\": \"{}\",\n  \"count\": {},\n  \"index\": {},\n  \"leaf\": {{\"type\": \"{}\", \"hash\": \"{}\"}},\n  \"path\": [{}]\n}}\n",
+            INCLUSION_PROOF, self.root, self.count, self.index, self.leaf.proof_type, self.leaf.hash, path
+        )
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Inclusion, String> {
+        let doc: Value = serde_json::from_slice(bytes)
+            .map_err(|_| "inclusion proof is not valid JSON".to_string())?;
+        if doc.get("@proof").and_then(Value::as_str) != Some(INCLUSION_PROOF) {
+            return Err(format!("@proof must be {}", INCLUSION_PROOF));
+        }
+        let number = |key: &str| {
+            doc.get(key)
+                .and_then(Value::as_u64)
+                .map(|n| n as usize)
+                .ok_or_else(|| format!("/{} must be an integer", key))
+        };
+        let count = number("count")?;
+        let index = number("index")?;
+        let entries = doc
+            .get("path")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "/path missing".to_string())?;
+        let mut path = Vec::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let side = match entry.get("side").and_then(Value::as_str) {
+                Some("left") => Side::Left,
+                Some("right") => Side::Right,
+                _ => return Err(format!("/path/{}/side must be left or right", i)),
+            };
+            path.push((side, digest_field(&doc, &format!("/path/{}/hash", i))?));
+        }
+        Ok(Inclusion {
+            root: digest_field(&doc, "/root_hash")?,
+            count,
+            index,
+            leaf: parse_leaf(&doc, "/leaf")?,
+            path,
+        })
+    }
+
+    /// Recomputes the root from the leaf proof bytes and the sibling path.
+    pub fn verify(&self, proof: &[u8]) -> Result<(), String> {
+        if self.index >= self.count {
+            return Err("inclusion index out of range".to_string());
+        }
+        let leaf = leaf_from_proof(proof).map_err(|e| format!("verify_each_leaf: {}", e))?;
+        if leaf != self.leaf {
+            return Err("verify_each_leaf: leaf hash mismatch".to_string());
+        }
+
+        // Replay the fold shape so the sides in the path are forced by index and count.
+        let mut node = leaf_node(&leaf);
+        let mut position = self.index;
+        let mut width = self.count;
+        let mut siblings = self.path.iter();
+        while width > 1 {
+            let sibling = position ^ 1;
+            if sibling < width {
+                let (side, hash) = siblings
+                    .next()
+                    .ok_or_else(|| "recompute_merkle_root: path too short".to_string())?;
+                let expected = if sibling < position {
+                    Side::Left
+                } else {
+                    Side::Right
+                };
+                if *side != expected {
+                    return Err("recompute_merkle_root: path side does not match index".to_string());
+                }
+                node = match side {
+                    Side::Left => inner_node(hash, &node),
+                    Side::Right => inner_node(&node, hash),
+                };
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+        if siblings.next().is_some() {
+            return Err("recompute_merkle_root: path too long".to_string());
+        }
+        if node != self.root {
+            return Err("compare_root_hash: root hash mismatch".to_string());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+// scxq2_pack.rs
+// SCXQ2 Lane Pack (DICT / FIELD / LANE / EDGE)
+// Version: v1
+//
+// Structured container for one CM-1 stream, following
+// docs/scxq2-lane-encodings-cm1-streams.md. Lanes appear in fixed order; LANE
+// stores the raw CM-1 bytes as spaced hex so the pack itself stays CM-1 clean.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::hash::{Algorithm, Digest};
+
+pub const PACK_SCHEMA: &str = "scxq2.pack.v1";
+pub const PACK_EXTENSION: &str = "scxq2";
+pub const LANES: [&str; 4] = ["DICT", "FIELD", "LANE", "EDGE"];
+pub const DEFAULT_BINDS: [&str; 2] = ["IDB.hash", "SCXQ2.proof"];
+pub const EDGE_HASH: Algorithm = Algorithm::Blake3;
+
+const DICT_JSON: &str = include_str!("src/scxq2/cm1.dict.json");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dict {
+    pub id: String,
+    pub bytes: BTreeMap<String, u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub collapse_geometry: String,
+    pub kernel: String,
+    pub cm1_profile: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub offset: u64,
+    pub length: u64,
+    pub hash: String,
+    pub binds: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pack {
+    pub dict: Dict,
+    pub field: Field,
+    pub lane: Vec<u8>,
+    pub edge: Edge,
+}
+
+fn json_escape(input: &str) -> String {
+    input
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
+
+pub fn parse_dict(doc: &Value) -> Result<Dict, String> {
+    let id = doc
+        .get("@id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "DICT: missing @id".to_string())?;
+    let table = doc
+        .get("bytes")
+        .and_then(Value::as_object)
+        .ok_or_else(|| "DICT: missing bytes".to_string())?;
+    let mut bytes = BTreeMap::new();
+    for (name, value) in table {
+        let byte = value
+            .as_u64()
+            .filter(|b| *b <= 0xFF)
+            .ok_or_else(|| format!("DICT: bytes.{} is not a byte", name))?;
+        bytes.insert(name.clone(), byte as u8);
+    }
+    Ok(Dict {
+        id: id.to_string(),
+        bytes,
+    })
+}
+
+/// The frozen CM-1 dictionary (src/scxq2/cm1.dict.json).
+pub fn frozen_dict() -> Dict {
+    let doc: Value = serde_json::from_str(DICT_JSON).expect("cm1.dict.json is valid JSON");
+    parse_dict(&doc).expect("cm1.dict.json is a valid DICT lane")
+}
+
+/// Parses whitespace-separated hex bytes ("01 02 0E ...").
+pub fn parse_hex(text: &str) -> Result<Vec<u8>, String> {
+    text.split_whitespace()
+        .map(|token| {
+            if token.len() != 2 {
+                return Err(format!("invalid hex byte {}", token));
+            }
+            u8::from_str_radix(token, 16).map_err(|_| format!("invalid hex byte {}", token))
+        })
+        .collect()
+}
+
+pub fn render_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|b| format!("{:02X}", b))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn lane(lanes: &[Value], index: usize) -> Result<&Value, String> {
+    let name = LANES[index];
+    let doc = lanes
+        .get(index)
+        .ok_or_else(|| format!("lanes[{}]: missing {}", index, name))?;
+    if doc.get("@lane").and_then(Value::as_str) != Some(name) {
+        return Err(format!("lanes[{}]: expected {}", index, name));
+    }
+    Ok(doc)
+}
+
+fn text(doc: &Value, lane: &str, key: &str) -> Result<String, String> {
+    doc.get(key)
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| format!("{}: missing {}", lane, key))
+}
+
+impl Pack {
+    /// Packs a CM-1 stream with the frozen DICT and a full-lane EDGE anchor.
+    pub fn new(field: Field, lane: Vec<u8>) -> Pack {
+        let edge = Edge {
+            offset: 0,
+            length: lane.len() as u64,
+            hash: Digest::of(EDGE_HASH, &lane).to_string(),
+            binds: DEFAULT_BINDS.iter().map(|s| s.to_string()).collect(),
+        };
+        Pack {
+            dict: frozen_dict(),
+            field,
+            lane,
+            edge,
+        }
+    }
+
+    /// Canonical pack text. Encoding the same pack twice yields identical bytes.
+    pub fn encode(&self) -> String {
+        let dict = self
+            .dict
+            .bytes
+            .iter()
+            .map(|(name, byte)| format!("\"{}\":{}", json_escape(name), byte))
+            .collect::<Vec<_>>()
+            .join(",");
+        let binds = self
+            .edge
+            .binds
+            .iter()
+            .map(|b| format!("\"{}\"", json_escape(b)))
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            "{{\n  \"@schema\": \"{}\",\n  \"lanes\": [\n    {{\"@lane\":\"DICT\",\"@id\":\"{}\",\"bytes\":{{{}}}}},\n    {{\"@lane\":\"FIELD\",\"collapse_geometry\":\"{}\",\"kernel\":\"{}\",\"cm1_profile\":\"{}\"}},\n    {{\"@lane\":\"LANE\",\"encoding\":\"hex\",\"bytes\":\"{}\"}},\n    {{\"@lane\":\"EDGE\",\"anchors\":[{{\"type\":\"cm1_offset\",\"value\":{}}},{{\"type\":\"cm1_length\",\"value\":{}}}],\"hash\":\"{}\",\"binds\":[{}]}}\n  ]\n}}\n",
+            PACK_SCHEMA,
+            json_escape(&self.dict.id),
+            dict,
+            json_escape(&self.field.collapse_geometry),
+            json_escape(&self.field.kernel),
+            json_escape(&self.field.cm1_profile),
+            render_hex(&self.lane),
+            self.edge.offset,
+            self.edge.length,
+            self.edge.hash,
+            binds
+        )
+    }
+
+    /// Reads a pack. Structure is checked here; lane consistency is `verify`.
+    pub fn decode(bytes: &[u8]) -> Result<Pack, String> {
+        let doc: Value =
+            serde_json::from_slice(bytes).map_err(|_| "pack is not valid JSON".to_string())?;
+        if doc.get("@schema").and_then(Value::as_str) != Some(PACK_SCHEMA) {
+            return Err("pack has wrong @schema".to_string());
+        }
+        let lanes = doc
+            .get("lanes")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "pack missing lanes".to_string())?;
+        if lanes.len() != LANES.len() {
+            return Err(format!("pack must hold exactly {} lanes", LANES.len()));
+        }
+
+        let dict = parse_dict(lane(lanes, 0)?)?;
+
+        let field_doc = lane(lanes, 1)?;
+        let field = Field {
+            collapse_geometry: text(field_doc, "FIELD", "collapse_geometry")?,
+            kernel: text(field_doc, "FIELD", "kernel")?,
+            cm1_profile: text(field_doc, "FIELD", "cm1_profile")?,
+        };
+
+        let lane_doc = lane(lanes, 2)?;
+        if text(lane_doc, "LANE", "encoding")? != "hex" {
+            return Err("LANE: unsupported encoding".to_string());
+        }
+        let lane_bytes =
+            parse_hex(&text(lane_doc, "LANE", "bytes")?).map_err(|e| format!("LANE: {}", e))?;
+
+        let edge_doc = lane(lanes, 3)?;
+        let anchors = edge_doc
+            .get("anchors")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "EDGE: missing anchors".to_string())?;
+        let mut offset = None;
+        let mut length = None;
+        for (i, anchor) in anchors.iter().enumerate() {
+            let value = anchor
+                .get("value")
+                .and_then(Value::as_u64)
+                .ok_or_else(|| format!("EDGE: anchors[{}].value is not an offset", i))?;
+            let slot = match anchor.get("type").and_then(Value::as_str) {
+                Some("cm1_offset") => &mut offset,
+                Some("cm1_length") => &mut length,
+                _ => return Err(format!("EDGE: anchors[{}] has unknown type", i)),
+            };
+            if slot.replace(value).is_some() {
+                return Err(format!("EDGE: anchors[{}] is a duplicate", i));
+            }
+        }
+        let binds = edge_doc
+            .get("binds")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "EDGE: missing binds".to_string())?
+            .iter()
+            .map(|b| b.as_str().map(str::to_string))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| "EDGE: binds must be strings".to_string())?;
+        let edge = Edge {
+            offset: offset.ok_or_else(|| "EDGE: missing cm1_offset anchor".to_string())?,
+            length: length.ok_or_else(|| "EDGE: missing cm1_length anchor".to_string())?,
+            hash: text(edge_doc, "EDGE", "hash")?,
+            binds,
+        };
+
+        Ok(Pack {
+            dict,
+            field,
+            lane: lane_bytes,
+            edge,
+        })
+    }
+
+    /// DICT must be the frozen CM-1 alphabet, EDGE anchors must fall inside
+    /// LANE, and the EDGE hash must bind the LANE bytes.
+    pub fn verify(&self) -> Result<(), String> {
+        if self.dict != frozen_dict() {
+            return Err("DICT differs from cm1.dict.v1".to_string());
+        }
+        let end = self.edge.offset.checked_add(self.edge.length);
+        if end.map(|end| end > self.lane.len() as u64).unwrap_or(true) {
+            return Err("EDGE anchor outside LANE".to_string());
+        }
+        Digest::check(&self.edge.hash, EDGE_HASH, &self.lane)
+            .map_err(|reason| format!("EDGE hash: {}", reason))?;
+        if self.edge.binds.is_empty() {
+            return Err("EDGE binds nothing".to_string());
+        }
+        Ok(())
+    }
+}
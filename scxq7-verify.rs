@@ -3,32 +3,60 @@
 // Version: v1.0.0 (frozen)
 // Verifier: scxq7.verify.v1
 
+mod axioms;
+mod binary_split;
+mod cluster_call;
+mod cm1_automaton;
+mod cm1_profile;
+mod conformance;
+mod exact;
+mod expr;
+mod geometry_binding;
+mod hash;
+mod idb;
+mod idb_diff;
+mod kernel_manifest;
+mod pi_adapter;
+mod pi_chain;
+mod pi_collapse;
+mod pi_conformance;
+mod pi_determinism;
+mod pi_gccp;
+mod pi_signal;
+mod registry;
+mod scxq2_aggregate;
+mod scxq2_pack;
+mod scxq7_cse;
+mod scxq7_object;
+
 use std::env;
 use std::fs::{self, File};
 use std::io::Read;
 use std::path::{Path, PathBuf};
 
-use sha2::{Digest, Sha256};
-
-const AXIOMS_DIR: &str = "axioms";
-const AXIOMS: [(&str, &str); 4] = [
-    (
-        "scxq7.schema.xjson",
-        "98a4394e1c761bcafcde5bbe8823ec157122e9a5f7649e7c67067252762a563e",
-    ),
-    (
-        "scxq2.schema.xjson",
-        "6bccfae8b876b356aa3dd1ade0402e16e1ffdaadcf30eb6b8b59a52131acf83d",
-    ),
-    (
-        "smca.schema.xjson",
-        "c809e83498dad5ae1e57635e36aa46f6c666c6594ad913d763237e8c4031d85f",
-    ),
-    (
-        "cm1.schema.xjson",
-        "8bd83645148e0564ffe4a8840c56b5228d52848df5a36711e17f340c2e54fc73",
-    ),
-];
+use axioms::AxiomLock;
+use cluster_call::{ClusterAxioms, ClusterCall};
+use cm1_profile::Profile;
+use conformance::Suite;
+use geometry_binding::GeometryRegistry;
+use hash::{Algorithm, Digest, Hasher};
+use idb::Idb;
+use kernel_manifest::GeometryAxioms;
+use pi_chain::Chain;
+use pi_collapse::Receipt;
+use pi_signal::{PiSignal, PiSignalView};
+use registry::Registry;
+use scxq2_aggregate::{Aggregate, Inclusion};
+use scxq2_pack::Pack;
+use scxq7_cse::Executor;
+use scxq7_object::Object;
+
+// Digest of axioms/axioms.lock; the lock pins every axiom file.
+const AXIOMS_LOCK_HASH: &str =
+    "sha256:2046ff5b3497c276a4d20c0d2ef0766ff0c6bc77468c069cd721bbf87b2635a8";
+
+const TARGET_HASH: Algorithm = Algorithm::Sha256;
+const MANIFEST_HASH: Algorithm = Algorithm::Sha3_256;
 
 const COMPLIANCE_VECTOR: &str = "{\"@verifier\":\"scxq7.verify.v1\",\"authority\":\"none\",\"deterministic\":true,\"offline\":true,\"projection_only\":true,\"reject_only\":true}";
 
@@ -59,18 +87,22 @@ impl Options {
     }
 }
 
+struct AxiomSet {
+    dir: PathBuf,
+    hash: Digest,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum TargetType {
     Directory,
     S7,
     Xjson,
     IdbXml,
-}
-
-fn sha256_hex(data: &[u8]) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(data);
-    format!("{:x}", hasher.finalize())
+    Scxq2,
+    PiSignal,
+    PiLane,
+    Object,
+    ClusterCall,
 }
 
 fn usage_error(msg: &str) -> ! {
@@ -104,16 +136,17 @@ fn json_escape(input: &str) -> String {
         .replace('\t', "\\t")
 }
 
-fn parse_args() -> (PathBuf, Options) {
-    let mut args: Vec<String> = env::args().skip(1).collect();
+fn parse_args(mut args: Vec<String>) -> (PathBuf, Options, Option<PathBuf>) {
     if args.first().map(|s| s == "verify").unwrap_or(false) {
         args.remove(0);
     }
 
     let mut opts = Options::new();
     let mut target: Option<PathBuf> = None;
+    let mut axioms_dir: Option<PathBuf> = None;
 
-    for arg in args {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
         if arg.starts_with("--") {
             match arg.as_str() {
                 "--emit-manifest" => opts.emit_manifest = true,
@@ -121,6 +154,12 @@ fn parse_args() -> (PathBuf, Options) {
                 "--strict" => opts.strict = true,
                 "--json" => opts.json = true,
                 "--quiet" => opts.quiet = true,
+                "--axioms" => {
+                    let dir = args.next().unwrap_or_else(|| {
+                        usage_error("usage error: --axioms requires a directory")
+                    });
+                    axioms_dir = Some(PathBuf::from(dir));
+                }
                 _ => usage_error("usage error: unknown option"),
             }
         } else if target.is_none() {
@@ -137,7 +176,7 @@ fn parse_args() -> (PathBuf, Options) {
         usage_error("usage error: forbidden input target");
     }
 
-    (target, opts)
+    (target, opts, axioms_dir)
 }
 
 fn detect_target_type(path: &Path) -> TargetType {
@@ -146,31 +185,45 @@ fn detect_target_type(path: &Path) -> TargetType {
     }
 
     let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
-    if file_name == "IDB.xml" {
+    if file_name == idb::IDB_FILE {
         return TargetType::IdbXml;
     }
+    if pi_adapter::is_signal(path) {
+        return TargetType::PiSignal;
+    }
+    if pi_signal::is_lane(path) {
+        return TargetType::PiLane;
+    }
+    if scxq7_object::is_object(path) {
+        return TargetType::Object;
+    }
+    if cluster_call::is_cluster_call(path) {
+        return TargetType::ClusterCall;
+    }
 
     match path.extension().and_then(|ext| ext.to_str()) {
         Some("s7") => TargetType::S7,
         Some("xjson") => TargetType::Xjson,
+        Some(scxq2_pack::PACK_EXTENSION) => TargetType::Scxq2,
         _ => usage_error("usage error: unsupported target type"),
     }
 }
 
-fn load_axioms(opts: Options) {
-    for (name, expected_hash) in AXIOMS.iter() {
-        let path = Path::new(AXIOMS_DIR).join(name);
-        let mut file = File::open(&path)
-            .unwrap_or_else(|_| fail(1, "schema validation failure: axioms missing", opts));
-        let mut bytes = Vec::new();
-        file.read_to_end(&mut bytes)
-            .unwrap_or_else(|_| fail(1, "schema validation failure: axioms unreadable", opts));
-        let actual = sha256_hex(&bytes);
-        if actual != *expected_hash {
-            let msg = format!("schema validation failure: axiom hash mismatch ({})", name);
-            fail(1, &msg, opts);
-        }
+fn resolve_axioms(start: &Path, explicit: Option<PathBuf>, opts: Options) -> PathBuf {
+    let dir = match explicit {
+        Some(dir) => fs::canonicalize(&dir).ok().filter(|d| d.is_dir()),
+        None => axioms::discover(start),
+    };
+    dir.unwrap_or_else(|| fail(1, "schema validation failure: axioms missing", opts))
+}
+
+fn load_axioms(dir: PathBuf, opts: Options) -> AxiomSet {
+    if let Err(reason) = axioms::verify(&dir, AXIOMS_LOCK_HASH) {
+        fail(1, &format!("schema validation failure: {}", reason), opts);
     }
+    let hash = axioms::combined_hash(&dir)
+        .unwrap_or_else(|reason| fail(1, &format!("schema validation failure: {}", reason), opts));
+    AxiomSet { dir, hash }
 }
 
 fn collect_files(root: &Path) -> Vec<PathBuf> {
@@ -208,11 +261,6 @@ fn has_forbidden_control(bytes: &[u8]) -> bool {
     })
 }
 
-fn contains_token_case_insensitive(bytes: &[u8], token: &str) -> bool {
-    let haystack = String::from_utf8_lossy(bytes).to_ascii_lowercase();
-    haystack.contains(&token.to_ascii_lowercase())
-}
-
 fn validate_xjson(bytes: &[u8], opts: Options) -> Result<(), String> {
     let trimmed = String::from_utf8_lossy(bytes).trim().to_string();
     if !(trimmed.starts_with('{') && trimmed.ends_with('}')) {
@@ -224,14 +272,6 @@ fn validate_xjson(bytes: &[u8], opts: Options) -> Result<(), String> {
     Ok(())
 }
 
-fn validate_idb_xml(bytes: &[u8]) -> Result<(), String> {
-    let trimmed = String::from_utf8_lossy(bytes).trim().to_string();
-    if !(trimmed.starts_with('<') && trimmed.ends_with('>')) {
-        return Err("invalid XML structure".to_string());
-    }
-    Ok(())
-}
-
 fn schema_validation(target: &Path, target_type: TargetType, opts: Options) {
     match target_type {
         TargetType::Directory => {
@@ -253,13 +293,53 @@ fn schema_validation(target: &Path, target_type: TargetType, opts: Options) {
                         }
                     }
                 }
-                if path.file_name().and_then(|n| n.to_str()) == Some("IDB.xml") {
+                if is_scxq2_pack(&path) {
+                    let bytes = read_bytes(&path, opts);
+                    if let Err(reason) = Pack::decode(&bytes) {
+                        let msg =
+                            format!("schema validation failure: {} ({})", reason, path.display());
+                        fail(1, &msg, opts);
+                    }
+                }
+                if pi_adapter::is_signal(&path) {
+                    let bytes = read_bytes(&path, opts);
+                    if let Err(reason) = pi_adapter::load(&bytes) {
+                        let msg =
+                            format!("schema validation failure: {} ({})", reason, path.display());
+                        fail(1, &msg, opts);
+                    }
+                }
+                if pi_signal::is_lane(&path) {
+                    let bytes = read_bytes(&path, opts);
+                    if let Err(reason) = PiSignalView::new(&bytes) {
+                        let msg =
+                            format!("schema validation failure: {} ({})", reason, path.display());
+                        fail(1, &msg, opts);
+                    }
+                }
+                if scxq7_object::is_object(&path) {
                     let bytes = read_bytes(&path, opts);
-                    if let Err(reason) = validate_idb_xml(&bytes) {
+                    if let Err(reason) = Object::parse(&bytes) {
+                        let msg =
+                            format!("schema validation failure: {} ({})", reason, path.display());
+                        fail(1, &msg, opts);
+                    }
+                }
+                if path.file_name().and_then(|n| n.to_str()) == Some(idb::IDB_FILE) {
+                    let bytes = read_bytes(&path, opts);
+                    if let Err(reason) = Idb::parse(&bytes) {
                         let msg = format!("schema validation failure: {} ({})", reason, path.display());
                         fail(1, &msg, opts);
                     }
                 }
+                if cluster_call::is_cluster_call(&path) {
+                    let bytes = read_bytes(&path, opts);
+                    if let Err(reason) = ClusterCall::parse(&bytes) {
+                        let msg =
+                            format!("schema validation failure: {} ({})", reason, path.display());
+                        fail(1, &msg, opts);
+                    }
+                }
             }
         }
         TargetType::Xjson => {
@@ -271,7 +351,42 @@ fn schema_validation(target: &Path, target_type: TargetType, opts: Options) {
         }
         TargetType::IdbXml => {
             let bytes = read_bytes(target, opts);
-            if let Err(reason) = validate_idb_xml(&bytes) {
+            if let Err(reason) = Idb::parse(&bytes) {
+                let msg = format!("schema validation failure: {}", reason);
+                fail(1, &msg, opts);
+            }
+        }
+        TargetType::Scxq2 => {
+            let bytes = read_bytes(target, opts);
+            if let Err(reason) = Pack::decode(&bytes) {
+                let msg = format!("schema validation failure: {}", reason);
+                fail(1, &msg, opts);
+            }
+        }
+        TargetType::PiSignal => {
+            let bytes = read_bytes(target, opts);
+            if let Err(reason) = pi_adapter::load(&bytes) {
+                let msg = format!("schema validation failure: {}", reason);
+                fail(1, &msg, opts);
+            }
+        }
+        TargetType::PiLane => {
+            let bytes = read_bytes(target, opts);
+            if let Err(reason) = PiSignalView::new(&bytes) {
+                let msg = format!("schema validation failure: {}", reason);
+                fail(1, &msg, opts);
+            }
+        }
+        TargetType::Object => {
+            let bytes = read_bytes(target, opts);
+            if let Err(reason) = Object::parse(&bytes) {
+                let msg = format!("schema validation failure: {}", reason);
+                fail(1, &msg, opts);
+            }
+        }
+        TargetType::ClusterCall => {
+            let bytes = read_bytes(target, opts);
+            if let Err(reason) = ClusterCall::parse(&bytes) {
                 let msg = format!("schema validation failure: {}", reason);
                 fail(1, &msg, opts);
             }
@@ -293,13 +408,18 @@ fn cm1_legality(target: &Path, target_type: TargetType, opts: Options) {
         }
     };
 
+    // π-signal lanes are binary; their bytes are checked by the SCXQ2 stage.
     match target_type {
         TargetType::Directory => {
             for path in collect_files(target) {
+                if pi_signal::is_lane(&path) {
+                    continue;
+                }
                 let bytes = read_bytes(&path, opts);
                 check_bytes(&bytes, &path);
             }
         }
+        TargetType::PiLane => {}
         _ => {
             let bytes = read_bytes(target, opts);
             check_bytes(&bytes, target);
@@ -307,31 +427,186 @@ fn cm1_legality(target: &Path, target_type: TargetType, opts: Options) {
     }
 }
 
-fn constraint_integrity(target: &Path, target_type: TargetType, opts: Options) {
+fn kernel_manifests(target: &Path, target_type: TargetType, axioms: &AxiomSet, opts: Options) {
+    let paths: Vec<PathBuf> = match target_type {
+        TargetType::Directory => collect_files(target)
+            .into_iter()
+            .filter(|p| kernel_manifest::is_kernel_manifest(p))
+            .collect(),
+        TargetType::Xjson if kernel_manifest::is_kernel_manifest(target) => {
+            vec![target.to_path_buf()]
+        }
+        _ => Vec::new(),
+    };
+    if paths.is_empty() {
+        return;
+    }
+
+    let schema_path = axioms.dir.join(kernel_manifest::GEOMETRY_SCHEMA_FILE);
+    let geometry = GeometryAxioms::load(&schema_path)
+        .unwrap_or_else(|reason| fail(1, &format!("schema validation failure: {}", reason), opts));
+
+    for path in paths {
+        let bytes = read_bytes(&path, opts);
+        let manifest: serde_json::Value = serde_json::from_slice(&bytes).unwrap_or_else(|_| {
+            let msg = format!(
+                "schema validation failure: invalid kernel manifest ({})",
+                path.display()
+            );
+            fail(1, &msg, opts)
+        });
+        if let Err(reason) = kernel_manifest::validate(&manifest, &geometry) {
+            let msg = format!("constraint violation: {} ({})", reason, path.display());
+            fail(3, &msg, opts);
+        }
+    }
+}
+
+fn cluster_calls(target: &Path, target_type: TargetType, opts: Options) {
+    let paths: Vec<PathBuf> = match target_type {
+        TargetType::Directory => collect_files(target)
+            .into_iter()
+            .filter(|p| cluster_call::is_cluster_call(p))
+            .collect(),
+        TargetType::ClusterCall => vec![target.to_path_buf()],
+        _ => Vec::new(),
+    };
+
+    for path in paths {
+        let toml_path = cluster_call::discover_axioms(&path).unwrap_or_else(|| {
+            let msg = format!(
+                "schema validation failure: {} missing ({})",
+                cluster_call::CLUSTER_AXIOMS_FILE,
+                path.display()
+            );
+            fail(1, &msg, opts)
+        });
+        let clusters = ClusterAxioms::load(&toml_path).unwrap_or_else(|reason| {
+            fail(1, &format!("schema validation failure: {}", reason), opts)
+        });
+        let bytes = read_bytes(&path, opts);
+        let call = ClusterCall::parse(&bytes).unwrap_or_else(|reason| {
+            let msg = format!("schema validation failure: {} ({})", reason, path.display());
+            fail(1, &msg, opts)
+        });
+        if let Err(reason) = call.check(&clusters) {
+            let msg = format!("constraint violation: {} ({})", reason, path.display());
+            fail(3, &msg, opts);
+        }
+    }
+}
+
+fn constraint_integrity(target: &Path, target_type: TargetType, axioms: &AxiomSet, opts: Options) {
+    kernel_manifests(target, target_type, axioms, opts);
+    cluster_calls(target, target_type, opts);
+
     if !opts.strict {
         return;
     }
 
-    let mut found = false;
-    match target_type {
-        TargetType::Directory => {
-            for path in collect_files(target) {
-                let bytes = read_bytes(&path, opts);
-                if contains_token_case_insensitive(&bytes, "scxq7") {
-                    found = true;
-                    break;
-                }
-            }
+    let objects = bound_objects(target, target_type, opts);
+    if objects.is_empty() {
+        fail(
+            3,
+            "constraint violation: no SCXQ2 pack or kernel manifest to resolve",
+            opts,
+        );
+    }
+
+    let registry_fail =
+        |reason: String| -> ! { fail(6, &format!("registry failure: {}", reason), opts) };
+    let root = registry::discover(target)
+        .unwrap_or_else(|| registry_fail("registry not found".to_string()));
+    let registry = Registry::load(&root).unwrap_or_else(|e| registry_fail(e));
+    let geometries_path = registry
+        .project_root()
+        .join(geometry_binding::GEOMETRY_REGISTRY_FILE);
+    let geometries = GeometryRegistry::load(&geometries_path).unwrap_or_else(|e| registry_fail(e));
+
+    for object in objects {
+        if let Err(reason) = resolve_object(&registry, &geometries, &object) {
+            let msg = format!(
+                "constraint violation: {} ({})",
+                reason,
+                object.path.display()
+            );
+            fail(3, &msg, opts);
         }
-        _ => {
-            let bytes = read_bytes(target, opts);
-            found = contains_token_case_insensitive(&bytes, "scxq7");
+    }
+}
+
+// An object that names registry entries: an SCXQ2 pack (its FIELD lane) or a
+// kernel manifest (no CM-1 profile).
+struct BoundObject {
+    path: PathBuf,
+    geometry: String,
+    kernel: String,
+    cm1_profile: Option<String>,
+}
+
+// Objects that do not decode are left to the schema and SCXQ2 stages.
+fn bound_objects(target: &Path, target_type: TargetType, opts: Options) -> Vec<BoundObject> {
+    let paths = match target_type {
+        TargetType::Directory => collect_files(target),
+        _ => vec![target.to_path_buf()],
+    };
+
+    let mut objects = Vec::new();
+    for path in paths {
+        if is_scxq2_pack(&path) {
+            if let Ok(pack) = Pack::decode(&read_bytes(&path, opts)) {
+                objects.push(BoundObject {
+                    path,
+                    geometry: pack.field.collapse_geometry,
+                    kernel: pack.field.kernel,
+                    cm1_profile: Some(pack.field.cm1_profile),
+                });
+            }
+        } else if kernel_manifest::is_kernel_manifest(&path) {
+            let manifest: serde_json::Value =
+                serde_json::from_slice(&read_bytes(&path, opts)).unwrap_or_default();
+            let text = |pointer: &str| {
+                manifest
+                    .pointer(pointer)
+                    .and_then(serde_json::Value::as_str)
+                    .map(str::to_string)
+            };
+            if let (Some(geometry), Some(kernel)) = (text("/@collapse/geometry"), text("/@kernel"))
+            {
+                objects.push(BoundObject {
+                    path,
+                    geometry,
+                    kernel,
+                    cm1_profile: None,
+                });
+            }
         }
     }
+    objects
+}
 
-    if !found {
-        fail(3, "constraint violation: missing scxq7 marker", opts);
+/// Resolves an object's kernel, geometry and CM-1 profile to registry entries.
+fn resolve_object(
+    registry: &Registry,
+    geometries: &GeometryRegistry,
+    object: &BoundObject,
+) -> Result<(), String> {
+    registry
+        .kernel(&object.kernel)
+        .ok_or_else(|| format!("unknown kernel {}", object.kernel))?;
+    let geometry = geometries.bind(&object.geometry, &object.kernel)?;
+    if let Some(profile) = &object.cm1_profile {
+        registry
+            .profile(profile)
+            .ok_or_else(|| format!("unknown cm1_profile {}", profile))?;
+        if *profile != geometry.cm1_profile {
+            return Err(format!(
+                "cm1_profile {}: geometry {} requires {}",
+                profile, geometry.name, geometry.cm1_profile
+            ));
+        }
     }
+    Ok(())
 }
 
 fn idb_anchoring(target: &Path, target_type: TargetType, opts: Options) {
@@ -340,7 +615,7 @@ fn idb_anchoring(target: &Path, target_type: TargetType, opts: Options) {
         TargetType::IdbXml => idb_paths.push(target.to_path_buf()),
         TargetType::Directory => {
             for path in collect_files(target) {
-                if path.file_name().and_then(|n| n.to_str()) == Some("IDB.xml") {
+                if path.file_name().and_then(|n| n.to_str()) == Some(idb::IDB_FILE) {
                     idb_paths.push(path);
                 }
             }
@@ -350,76 +625,139 @@ fn idb_anchoring(target: &Path, target_type: TargetType, opts: Options) {
 
     for path in idb_paths {
         let bytes = read_bytes(&path, opts);
-        let content = String::from_utf8_lossy(&bytes).to_ascii_lowercase();
-        if !content.contains("hash") || !content.contains("parent") {
-            let msg = format!("IDB anchoring failure: hash continuity violation ({})", path.display());
+        if let Err(reason) = Idb::parse(&bytes).and_then(|idb| idb.check_continuity()) {
+            let msg = format!(
+                "IDB anchoring failure: hash continuity violation: {} ({})",
+                reason,
+                path.display()
+            );
             fail(4, &msg, opts);
         }
     }
 }
 
+fn is_scxq2_pack(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some(scxq2_pack::PACK_EXTENSION)
+}
+
 fn scxq2_packing(target: &Path, target_type: TargetType, opts: Options) {
-    if !opts.strict {
-        return;
+    let packs: Vec<PathBuf> = match target_type {
+        TargetType::Directory => collect_files(target)
+            .into_iter()
+            .filter(|p| is_scxq2_pack(p))
+            .collect(),
+        TargetType::Scxq2 => vec![target.to_path_buf()],
+        _ => Vec::new(),
+    };
+
+    if opts.strict && packs.is_empty() {
+        fail(5, "SCXQ2 failure: lane pack missing", opts);
     }
 
-    let mut found = false;
-    match target_type {
-        TargetType::Directory => {
-            for path in collect_files(target) {
-                let bytes = read_bytes(&path, opts);
-                if contains_token_case_insensitive(&bytes, "scxq2") {
-                    found = true;
-                    break;
-                }
-            }
-        }
-        _ => {
-            let bytes = read_bytes(target, opts);
-            found = contains_token_case_insensitive(&bytes, "scxq2");
+    for path in packs {
+        let bytes = read_bytes(&path, opts);
+        let checked = Pack::decode(&bytes).and_then(|pack| pack.verify());
+        if let Err(reason) = checked {
+            let msg = format!("SCXQ2 failure: {} ({})", reason, path.display());
+            fail(5, &msg, opts);
         }
     }
 
-    if !found {
-        fail(5, "SCXQ2 failure: lane packing marker missing", opts);
+    let lanes: Vec<PathBuf> = match target_type {
+        TargetType::Directory => collect_files(target)
+            .into_iter()
+            .filter(|p| pi_signal::is_lane(p))
+            .collect(),
+        TargetType::PiLane => vec![target.to_path_buf()],
+        _ => Vec::new(),
+    };
+    for path in lanes {
+        let bytes = read_bytes(&path, opts);
+        let checked = PiSignalView::new(&bytes).and_then(|view| view.verify());
+        if let Err(reason) = checked {
+            let msg = format!("SCXQ2 failure: {} ({})", reason, path.display());
+            fail(5, &msg, opts);
+        }
     }
 }
 
-fn target_hash(target: &Path, target_type: TargetType, opts: Options) -> String {
+fn target_hash(target: &Path, target_type: TargetType, opts: Options) -> Digest {
     match target_type {
         TargetType::Directory => {
-            let mut hasher = Sha256::new();
+            let mut hasher = Hasher::new(TARGET_HASH);
             for path in collect_files(target) {
                 let rel = path.strip_prefix(target).unwrap_or(&path);
                 hasher.update(rel.to_string_lossy().as_bytes());
                 hasher.update(b"\0");
                 let bytes = read_bytes(&path, opts);
-                let file_hash = sha256_hex(&bytes);
-                hasher.update(file_hash.as_bytes());
+                let file_hash = Digest::of(TARGET_HASH, &bytes);
+                hasher.update(file_hash.hex().as_bytes());
                 hasher.update(b"\0");
             }
-            format!("{:x}", hasher.finalize())
+            hasher.finalize()
         }
         _ => {
             let bytes = read_bytes(target, opts);
-            sha256_hex(&bytes)
+            Digest::of(TARGET_HASH, &bytes)
+        }
+    }
+}
+
+// The axioms directory as seen from the target's directory, so the manifest
+// (and its hash) does not depend on where the checkout lives.
+fn axioms_relative(target: &Path, axioms_dir: &Path) -> String {
+    let base = if target.is_dir() {
+        target
+    } else {
+        target.parent().unwrap_or(Path::new("."))
+    };
+    let base = fs::canonicalize(base).unwrap_or_else(|_| base.to_path_buf());
+    let mut base_parts = base.components().peekable();
+    let mut dir_parts = axioms_dir.components().peekable();
+    while let (Some(a), Some(b)) = (base_parts.peek(), dir_parts.peek()) {
+        if a != b {
+            break;
         }
+        base_parts.next();
+        dir_parts.next();
     }
+    let mut relative = PathBuf::new();
+    for _ in base_parts {
+        relative.push("..");
+    }
+    relative.extend(dir_parts);
+    if relative.as_os_str().is_empty() {
+        return ".".to_string();
+    }
+    relative.to_string_lossy().to_string()
 }
 
-fn emit_manifest(target: &Path, target_type: TargetType, target_hash: &str, opts: Options) -> String {
+fn emit_manifest(
+    target: &Path,
+    target_type: TargetType,
+    target_hash: &Digest,
+    axioms: &AxiomSet,
+    opts: Options,
+) -> Digest {
     let target_type_str = match target_type {
         TargetType::Directory => "directory",
         TargetType::S7 => ".s7",
         TargetType::Xjson => ".xjson",
         TargetType::IdbXml => "IDB.xml",
+        TargetType::Scxq2 => ".scxq2",
+        TargetType::PiSignal => pi_adapter::SIGNAL_SUFFIX,
+        TargetType::PiLane => pi_signal::LANE_SUFFIX,
+        TargetType::Object => scxq7_object::OBJECT_SCHEMA,
+        TargetType::ClusterCall => cluster_call::CLUSTER_CALL_TYPE,
     };
 
     let manifest = format!(
-        "{{\n  \"@schema\": \"scxq7://verified-manifest/v1\",\n  \"result\": \"COMPLIANT\",\n  \"target\": \"{}\",\n  \"target_type\": \"{}\",\n  \"target_hash\": \"{}\",\n  \"logical_time\": 1,\n  \"steps\": [\n    \"{}\",\n    \"{}\",\n    \"{}\",\n    \"{}\",\n    \"{}\"\n  ],\n  \"vector\": {}\n}}\n",
+        "{{\n  \"@schema\": \"scxq7://verified-manifest/v1\",\n  \"result\": \"COMPLIANT\",\n  \"target\": \"{}\",\n  \"target_type\": \"{}\",\n  \"target_hash\": \"{}\",\n  \"axioms\": \"{}\",\n  \"axioms_hash\": \"{}\",\n  \"logical_time\": 1,\n  \"steps\": [\n    \"{}\",\n    \"{}\",\n    \"{}\",\n    \"{}\",\n    \"{}\"\n  ],\n  \"vector\": {}\n}}\n",
         json_escape(&target.to_string_lossy()),
         target_type_str,
         target_hash,
+        json_escape(&axioms_relative(target, &axioms.dir)),
+        axioms.hash,
         STEP_SCHEMA,
         STEP_CM1,
         STEP_CONSTRAINT,
@@ -431,10 +769,10 @@ fn emit_manifest(target: &Path, target_type: TargetType, target_hash: &str, opts
     fs::write("verified.manifest.xjson", manifest.as_bytes())
         .unwrap_or_else(|_| fail(1, "schema validation failure: cannot write manifest", opts));
 
-    sha256_hex(manifest.as_bytes())
+    Digest::of(MANIFEST_HASH, manifest.as_bytes())
 }
 
-fn emit_badges(manifest_hash: &str, opts: Options) {
+fn emit_badges(manifest_hash: &Digest, opts: Options) {
     fs::create_dir_all("badges")
         .unwrap_or_else(|_| fail(1, "schema validation failure: cannot create badges", opts));
 
@@ -447,22 +785,35 @@ fn emit_badges(manifest_hash: &str, opts: Options) {
         .unwrap_or_else(|_| fail(1, "schema validation failure: cannot write badge", opts));
 }
 
-fn emit_success(opts: Options, target: &Path, target_type: TargetType, target_hash: &str) {
+fn emit_success(
+    opts: Options,
+    target: &Path,
+    target_type: TargetType,
+    target_hash: &Digest,
+    axioms: &AxiomSet,
+) {
     if opts.quiet {
         return;
     }
 
     if opts.json {
         let payload = format!(
-            "{{\"result\":\"COMPLIANT\",\"target\":\"{}\",\"target_type\":\"{}\",\"target_hash\":\"{}\",\"steps\":[\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"],\"vector\":{}}}",
+            "{{\"result\":\"COMPLIANT\",\"target\":\"{}\",\"target_type\":\"{}\",\"target_hash\":\"{}\",\"axioms\":\"{}\",\"axioms_hash\":\"{}\",\"steps\":[\"{}\",\"{}\",\"{}\",\"{}\",\"{}\"],\"vector\":{}}}",
             json_escape(&target.to_string_lossy()),
             match target_type {
                 TargetType::Directory => "directory",
                 TargetType::S7 => ".s7",
                 TargetType::Xjson => ".xjson",
                 TargetType::IdbXml => "IDB.xml",
+                TargetType::Scxq2 => ".scxq2",
+                TargetType::PiSignal => pi_adapter::SIGNAL_SUFFIX,
+                TargetType::PiLane => pi_signal::LANE_SUFFIX,
+                TargetType::Object => scxq7_object::OBJECT_SCHEMA,
+                TargetType::ClusterCall => cluster_call::CLUSTER_CALL_TYPE,
             },
             target_hash,
+            json_escape(&axioms_relative(target, &axioms.dir)),
+            axioms.hash,
             STEP_SCHEMA,
             STEP_CM1,
             STEP_CONSTRAINT,
@@ -482,14 +833,1215 @@ fn emit_success(opts: Options, target: &Path, target_type: TargetType, target_ha
     println!("\nRESULT: COMPLIANT");
 }
 
+fn run_registry(args: Vec<String>) {
+    let (root, opts, axioms_dir) = parse_args(args);
+    if axioms_dir.is_some() {
+        usage_error("usage error: --axioms does not apply to registry");
+    }
+    if !root.is_dir() {
+        usage_error("usage error: registry target must be a directory");
+    }
+
+    let registry = Registry::load(&root)
+        .unwrap_or_else(|reason| fail(6, &format!("registry failure: {}", reason), opts));
+    let issues = registry.dangling();
+    if let Some((last, rest)) = issues.split_last() {
+        if !opts.json {
+            for issue in rest {
+                eprintln!(
+                    "✘ dangling reference: {} ({})",
+                    issue.message,
+                    issue.path.display()
+                );
+            }
+        }
+        let msg = format!(
+            "dangling reference: {} ({})",
+            last.message,
+            last.path.display()
+        );
+        fail(6, &msg, opts);
+    }
+
+    if opts.quiet {
+        return;
+    }
+    if opts.json {
+        println!(
+            "{{\"result\":\"COMPLIANT\",\"registry\":\"{}\",\"kernels\":{},\"profiles\":{},\"geometry_versions\":{},\"vector\":{}}}",
+            json_escape(&root.to_string_lossy()),
+            registry.kernels.len(),
+            registry.profiles.len(),
+            registry.geometry_versions.len(),
+            COMPLIANCE_VECTOR
+        );
+        return;
+    }
+    for kernel in registry.kernels.values() {
+        let id = &kernel.id;
+        let (profile, _) = registry
+            .kernel_profile(id)
+            .unwrap_or_else(|reason| fail(6, &format!("registry failure: {}", reason), opts));
+        match kernel.collapse_geometry.as_deref() {
+            Some(geometry) => println!("✔ kernel {} → {} ({})", id, profile, geometry),
+            None => println!("✔ kernel {} → {}", id, profile),
+        }
+    }
+    let geometry = registry
+        .geometry(None)
+        .unwrap_or_else(|reason| fail(6, &format!("registry failure: {}", reason), opts));
+    println!(
+        "✔ collapse geometry {} → {} ({}, {})",
+        geometry.version,
+        geometry.path.display(),
+        geometry
+            .doc
+            .get("@schema")
+            .and_then(|s| s.as_str())
+            .unwrap_or("-"),
+        geometry.hash
+    );
+    println!("\nRESULT: COMPLIANT");
+}
+
+fn run_bindings(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut root: Option<PathBuf> = None;
+    let mut geometries: Option<PathBuf> = None;
+    let mut axioms_dir: Option<PathBuf> = None;
+    let mut geometry: Option<String> = None;
+    let mut kernel: Option<String> = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage_error("usage error: option requires a value"))
+        };
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            "--geometries" => geometries = Some(PathBuf::from(value())),
+            "--axioms" => axioms_dir = Some(PathBuf::from(value())),
+            "--geometry" => geometry = Some(value()),
+            "--kernel" => kernel = Some(value()),
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ if root.is_none() => root = Some(PathBuf::from(arg)),
+            _ => usage_error("usage error: too many targets"),
+        }
+    }
+    let root = root.unwrap_or_else(|| usage_error("usage error: missing target"));
+    if !root.is_dir() {
+        usage_error("usage error: registry target must be a directory");
+    }
+    let registry_fail =
+        |reason: String| -> ! { fail(6, &format!("registry failure: {}", reason), opts) };
+
+    let registry = Registry::load(&root).unwrap_or_else(|e| registry_fail(e));
+    let geometries_path = geometries.unwrap_or_else(|| {
+        registry
+            .project_root()
+            .join(geometry_binding::GEOMETRY_REGISTRY_FILE)
+    });
+    let geometries = GeometryRegistry::load(&geometries_path).unwrap_or_else(|e| registry_fail(e));
+
+    if geometry.is_some() || kernel.is_some() {
+        let (geometry, kernel) = match (geometry, kernel) {
+            (Some(g), Some(k)) => (g, k),
+            _ => usage_error("usage error: --geometry and --kernel go together"),
+        };
+        let bound = geometries
+            .bind(&geometry, &kernel)
+            .unwrap_or_else(|e| registry_fail(format!("binding contradiction: {}", e)));
+        if opts.quiet {
+            return;
+        }
+        if opts.json {
+            println!(
+                "{{\"result\":\"COMPLIANT\",\"geometry\":\"{}\",\"kernel\":\"{}\",\"cm1_profile\":\"{}\",\"vector\":{}}}",
+                json_escape(&bound.name),
+                json_escape(&kernel),
+                json_escape(&bound.cm1_profile),
+                COMPLIANCE_VECTOR
+            );
+            return;
+        }
+        println!("✔ {} allows {} → {}", bound.name, kernel, bound.cm1_profile);
+        println!("\nRESULT: COMPLIANT");
+        return;
+    }
+
+    let axioms = load_axioms(resolve_axioms(&root, axioms_dir, opts), opts);
+    let geometry_axioms =
+        GeometryAxioms::load(&axioms.dir.join(kernel_manifest::GEOMETRY_SCHEMA_FILE))
+            .unwrap_or_else(|e| fail(1, &format!("schema validation failure: {}", e), opts));
+    let issues = geometry_binding::contradictions(&registry, &geometries, Some(&geometry_axioms));
+    if let Some((last, rest)) = issues.split_last() {
+        if !opts.json {
+            for issue in rest {
+                eprintln!(
+                    "✘ binding contradiction: {} ({})",
+                    issue.message,
+                    issue.path.display()
+                );
+            }
+        }
+        registry_fail(format!(
+            "binding contradiction: {} ({})",
+            last.message,
+            last.path.display()
+        ));
+    }
+
+    if opts.quiet {
+        return;
+    }
+    if opts.json {
+        println!(
+            "{{\"result\":\"COMPLIANT\",\"geometries\":{},\"kernels\":{},\"vector\":{}}}",
+            geometries.geometries.len(),
+            registry.kernels.len(),
+            COMPLIANCE_VECTOR
+        );
+        return;
+    }
+    for geometry in geometries.geometries.values() {
+        println!(
+            "✔ geometry {} → {} [{}]",
+            geometry.name,
+            geometry.cm1_profile,
+            geometry.allowed_kernels.join(", ")
+        );
+    }
+    println!("✔ {} kernels bound", registry.kernels.len());
+    println!("\nRESULT: COMPLIANT");
+}
+
+fn run_conformance(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut suites: Vec<PathBuf> = Vec::new();
+    let mut root: Option<PathBuf> = None;
+    let mut geometries: Option<PathBuf> = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage_error("usage error: option requires a value"))
+        };
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            "--registry" => root = Some(PathBuf::from(value())),
+            "--geometries" => geometries = Some(PathBuf::from(value())),
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ => suites.push(PathBuf::from(arg)),
+        }
+    }
+    if suites.is_empty() {
+        usage_error("usage error: missing target");
+    }
+    let registry_fail =
+        |reason: String| -> ! { fail(6, &format!("registry failure: {}", reason), opts) };
+
+    let root = root
+        .or_else(|| registry::discover(&suites[0]))
+        .unwrap_or_else(|| registry_fail("registry not found (use --registry)".to_string()));
+    let registry = Registry::load(&root).unwrap_or_else(|e| registry_fail(e));
+    let geometries_path = geometries.unwrap_or_else(|| {
+        registry
+            .project_root()
+            .join(geometry_binding::GEOMETRY_REGISTRY_FILE)
+    });
+    let geometries = GeometryRegistry::load(&geometries_path).unwrap_or_else(|e| registry_fail(e));
+
+    let mut outcomes = Vec::new();
+    for path in &suites {
+        let origin = path.display().to_string();
+        let suite = Suite::parse(&read_bytes(path, opts), &origin)
+            .unwrap_or_else(|e| fail(1, &format!("schema validation failure: {}", e), opts));
+        let results = suite
+            .run(&registry, &geometries)
+            .unwrap_or_else(|e| registry_fail(format!("{} ({})", e, origin)));
+        outcomes.extend(
+            results
+                .into_iter()
+                .map(|outcome| (suite.name.clone(), outcome)),
+        );
+    }
+
+    let failed: Vec<_> = outcomes
+        .iter()
+        .filter(|(_, outcome)| !outcome.passed())
+        .collect();
+    if let Some(((last_suite, last), rest)) = failed.split_last() {
+        let describe = |suite: &str, outcome: &conformance::Outcome| {
+            format!(
+                "conformance vector {}/{} expected {}, got {}{}",
+                suite,
+                outcome.name,
+                outcome.expect,
+                outcome.actual,
+                outcome
+                    .reason
+                    .as_deref()
+                    .map(|r| format!(" ({})", r))
+                    .unwrap_or_default()
+            )
+        };
+        if !opts.json {
+            for (suite, outcome) in rest {
+                eprintln!("✘ CM-1 violation: {}", describe(suite, outcome));
+            }
+        }
+        fail(
+            2,
+            &format!("CM-1 violation: {}", describe(last_suite, last)),
+            opts,
+        );
+    }
+
+    if opts.quiet {
+        return;
+    }
+    if opts.json {
+        println!(
+            "{{\"result\":\"COMPLIANT\",\"suites\":{},\"vectors\":{},\"vector\":{}}}",
+            suites.len(),
+            outcomes.len(),
+            COMPLIANCE_VECTOR
+        );
+        return;
+    }
+    for (suite, outcome) in &outcomes {
+        match &outcome.reason {
+            Some(reason) => println!(
+                "✔ {}/{} → {} ({})",
+                suite, outcome.name, outcome.actual, reason
+            ),
+            None => println!("✔ {}/{} → {}", suite, outcome.name, outcome.actual),
+        }
+    }
+    println!("\nRESULT: COMPLIANT");
+}
+
+fn run_profile(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut stream: Option<PathBuf> = None;
+    let mut root: Option<PathBuf> = None;
+    let mut name: Option<String> = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .unwrap_or_else(|| usage_error("usage error: option requires a value"))
+        };
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            "--registry" => root = Some(PathBuf::from(value())),
+            "--profile" => name = Some(value()),
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ if stream.is_none() => stream = Some(PathBuf::from(arg)),
+            _ => usage_error("usage error: too many targets"),
+        }
+    }
+    let stream = stream.unwrap_or_else(|| usage_error("usage error: missing target"));
+    let name = name.unwrap_or_else(|| usage_error("usage error: missing --profile"));
+    let registry_fail =
+        |reason: String| -> ! { fail(6, &format!("registry failure: {}", reason), opts) };
+
+    let root = root
+        .or_else(|| registry::discover(&stream))
+        .unwrap_or_else(|| registry_fail("registry not found (use --registry)".to_string()));
+    let registry = Registry::load(&root).unwrap_or_else(|e| registry_fail(e));
+    let profile = Profile::resolve(&registry, &name).unwrap_or_else(|e| registry_fail(e));
+
+    let text = String::from_utf8_lossy(&read_bytes(&stream, opts)).to_string();
+    let bytes = scxq2_pack::parse_hex(&text)
+        .unwrap_or_else(|e| fail(5, &format!("SCXQ2 failure: {}", e), opts));
+    profile
+        .check(&bytes)
+        .unwrap_or_else(|e| fail(2, &format!("CM-1 violation: {}", e), opts));
+
+    if opts.quiet {
+        return;
+    }
+    if opts.json {
+        println!(
+            "{{\"result\":\"COMPLIANT\",\"profile\":\"{}\",\"bytes\":{},\"vector\":{}}}",
+            json_escape(&profile.name),
+            bytes.len(),
+            COMPLIANCE_VECTOR
+        );
+        return;
+    }
+    println!("✔ {} bytes legal under {}", bytes.len(), profile.name);
+    println!("\nRESULT: COMPLIANT");
+}
+
+fn run_axioms(mut args: Vec<String>) {
+    if args.is_empty() {
+        usage_error("usage error: axioms requires list, unpinned or lock");
+    }
+    let command = args.remove(0);
+    let mut opts = Options::new();
+    let mut explicit: Option<PathBuf> = None;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            "--axioms" => {
+                let dir = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("usage error: --axioms requires a directory"));
+                explicit = Some(PathBuf::from(dir));
+            }
+            _ => usage_error("usage error: unknown option"),
+        }
+    }
+
+    let dir = resolve_axioms(Path::new("."), explicit, opts);
+    let found = axioms::scan(&dir)
+        .unwrap_or_else(|reason| fail(1, &format!("schema validation failure: {}", reason), opts));
+    let lock = match fs::read(dir.join(axioms::LOCK_FILE)) {
+        Ok(bytes) => AxiomLock::parse(&bytes).unwrap_or_else(|reason| {
+            fail(1, &format!("schema validation failure: {}", reason), opts)
+        }),
+        Err(_) => AxiomLock::from_scan(Default::default()),
+    };
+
+    match command.as_str() {
+        "list" | "unpinned" => {
+            let statuses = lock.statuses(&found);
+            let shown: Vec<&(String, axioms::Status)> = statuses
+                .iter()
+                .filter(|(_, status)| command == "list" || *status == axioms::Status::Unpinned)
+                .collect();
+            if !opts.quiet {
+                for (name, status) in &shown {
+                    let (mark, label) = match status {
+                        axioms::Status::Pinned => ("✔", "pinned"),
+                        axioms::Status::Mismatch => ("✘", "hash mismatch"),
+                        axioms::Status::Missing => ("✘", "missing"),
+                        axioms::Status::Unpinned => ("✘", "unpinned"),
+                    };
+                    if opts.json {
+                        println!(
+                            "{{\"axiom\":\"{}\",\"status\":\"{}\"}}",
+                            json_escape(name),
+                            label
+                        );
+                    } else {
+                        println!("{} {} ({})", mark, name, label);
+                    }
+                }
+            }
+            if shown
+                .iter()
+                .any(|(_, status)| *status != axioms::Status::Pinned)
+            {
+                std::process::exit(1);
+            }
+        }
+        "lock" => {
+            let rendered = AxiomLock::from_scan(found).render();
+            print!("{}", rendered);
+            eprintln!(
+                "{} {}",
+                axioms::LOCK_FILE,
+                Digest::of(axioms::LOCK_HASH, rendered.as_bytes())
+            );
+        }
+        _ => usage_error("usage error: axioms requires list, unpinned or lock"),
+    }
+}
+
+fn run_pack(args: Vec<String>) {
+    let mut source: Option<PathBuf> = None;
+    let mut geometry: Option<String> = None;
+    let mut kernel: Option<String> = None;
+    let mut profile: Option<String> = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let slot = match arg.as_str() {
+            "--geometry" => &mut geometry,
+            "--kernel" => &mut kernel,
+            "--profile" => &mut profile,
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ if source.is_none() => {
+                source = Some(PathBuf::from(arg));
+                continue;
+            }
+            _ => usage_error("usage error: too many targets"),
+        };
+        *slot = Some(
+            args.next()
+                .unwrap_or_else(|| usage_error("usage error: option requires a value")),
+        );
+    }
+
+    let opts = Options::new();
+    let source = source.unwrap_or_else(|| usage_error("usage error: missing target"));
+    let field = scxq2_pack::Field {
+        collapse_geometry: geometry
+            .unwrap_or_else(|| usage_error("usage error: missing --geometry")),
+        kernel: kernel.unwrap_or_else(|| usage_error("usage error: missing --kernel")),
+        cm1_profile: profile.unwrap_or_else(|| usage_error("usage error: missing --profile")),
+    };
+
+    let text = String::from_utf8_lossy(&read_bytes(&source, opts)).to_string();
+    let lane = scxq2_pack::parse_hex(&text)
+        .unwrap_or_else(|reason| fail(5, &format!("SCXQ2 failure: {}", reason), opts));
+    print!("{}", Pack::new(field, lane).encode());
+}
+
+fn run_aggregate(mut args: Vec<String>) {
+    const USAGE: &str = "usage error: aggregate requires build, prove, verify or verify-inclusion";
+    if args.is_empty() {
+        usage_error(USAGE);
+    }
+    let command = args.remove(0);
+    let mut opts = Options::new();
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    let scxq2_fail = |reason: String| -> ! { fail(5, &format!("SCXQ2 failure: {}", reason), opts) };
+
+    match command.as_str() {
+        "build" => {
+            if paths.is_empty() || !paths.len().is_multiple_of(2) {
+                usage_error("usage error: aggregate build <receipt> <lane> [<receipt> <lane>]...");
+            }
+            let inputs: Vec<(Vec<u8>, Vec<u8>)> = paths
+                .chunks(2)
+                .map(|pair| (read_bytes(&pair[0], opts), read_bytes(&pair[1], opts)))
+                .collect();
+            let aggregate = Aggregate::build(&inputs).unwrap_or_else(|e| scxq2_fail(e));
+            print!("{}", aggregate.render());
+            return;
+        }
+        "prove" => {
+            if paths.len() != 2 {
+                usage_error("usage error: aggregate prove <aggregate> <index>");
+            }
+            let index: usize = paths[1]
+                .to_string_lossy()
+                .parse()
+                .unwrap_or_else(|_| usage_error("usage error: index must be an integer"));
+            let aggregate =
+                Aggregate::parse(&read_bytes(&paths[0], opts)).unwrap_or_else(|e| scxq2_fail(e));
+            let inclusion = aggregate.inclusion(index).unwrap_or_else(|e| scxq2_fail(e));
+            print!("{}", inclusion.render());
+            return;
+        }
+        "verify" => {
+            if paths.len() < 3 || paths.len().is_multiple_of(2) {
+                usage_error(
+                    "usage error: aggregate verify <aggregate> <receipt> <lane> [<receipt> <lane>]...",
+                );
+            }
+            let aggregate =
+                Aggregate::parse(&read_bytes(&paths[0], opts)).unwrap_or_else(|e| scxq2_fail(e));
+            let inputs: Vec<(Vec<u8>, Vec<u8>)> = paths[1..]
+                .chunks(2)
+                .map(|pair| (read_bytes(&pair[0], opts), read_bytes(&pair[1], opts)))
+                .collect();
+            aggregate.verify(&inputs).unwrap_or_else(|e| scxq2_fail(e));
+        }
+        "verify-inclusion" => {
+            if paths.len() != 2 {
+                usage_error("usage error: aggregate verify-inclusion <inclusion> <proof>");
+            }
+            let inclusion =
+                Inclusion::parse(&read_bytes(&paths[0], opts)).unwrap_or_else(|e| scxq2_fail(e));
+            inclusion
+                .verify(&read_bytes(&paths[1], opts))
+                .unwrap_or_else(|e| scxq2_fail(e));
+        }
+        _ => usage_error(USAGE),
+    }
+
+    if opts.quiet {
+        return;
+    }
+    if opts.json {
+        println!(
+            "{{\"result\":\"COMPLIANT\",\"proof\":\"{}\",\"steps\":[\"{}\"],\"vector\":{}}}",
+            scxq2_aggregate::AGGREGATE_PROOF,
+            scxq2_aggregate::VERIFICATION_STEPS.join("\",\""),
+            COMPLIANCE_VECTOR
+        );
+        return;
+    }
+    for step in scxq2_aggregate::VERIFICATION_STEPS {
+        println!("✔ {}", step);
+    }
+    println!("\nRESULT: COMPLIANT");
+}
+
+fn run_pi_signal(mut args: Vec<String>) {
+    const USAGE: &str = "usage error: pi-signal requires encode, verify, collapse or replay";
+    if args.is_empty() {
+        usage_error(USAGE);
+    }
+    let command = args.remove(0);
+    let mut opts = Options::new();
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    let expected = if command == "replay" { 2 } else { 1 };
+    if paths.len() != expected {
+        usage_error(match command.as_str() {
+            "replay" => "usage error: pi-signal replay <receipt> <lane>",
+            _ if paths.is_empty() => "usage error: missing target",
+            _ => "usage error: too many targets",
+        });
+    }
+    let scxq2_fail = |reason: String| -> ! { fail(5, &format!("SCXQ2 failure: {}", reason), opts) };
+    let bytes = read_bytes(&paths[0], opts);
+
+    match command.as_str() {
+        "encode" => {
+            use std::io::Write;
+            let signal = PiSignal::from_json(&bytes).unwrap_or_else(|e| scxq2_fail(e));
+            std::io::stdout()
+                .write_all(&signal.encode())
+                .unwrap_or_else(|_| fail(5, "SCXQ2 failure: cannot write lane", opts));
+            return;
+        }
+        "collapse" => {
+            let receipt = Receipt::collapse(&bytes).unwrap_or_else(|e| scxq2_fail(e));
+            print!("{}", receipt.render());
+            return;
+        }
+        "replay" => {
+            let receipt = Receipt::parse(&bytes).unwrap_or_else(|e| scxq2_fail(e));
+            receipt
+                .replay(&read_bytes(&paths[1], opts))
+                .unwrap_or_else(|e| scxq2_fail(e));
+            if opts.quiet {
+                return;
+            }
+            if opts.json {
+                println!(
+                    "{{\"result\":\"COMPLIANT\",\"proof\":\"{}\",\"lane_hash\":\"{}\",\"value\":\"{:?}\",\"tolerance\":0,\"vector\":{}}}",
+                    pi_collapse::PROOF_TYPE,
+                    receipt.lane_hash,
+                    receipt.value(),
+                    COMPLIANCE_VECTOR
+                );
+                return;
+            }
+            println!("✔ Input Lane Hash ({})", receipt.lane_hash);
+            for op in pi_collapse::OPERATIONS {
+                println!("✔ Replay {}", op);
+            }
+            println!("✔ Output {:?} (tolerance 0)", receipt.value());
+            println!("\nRESULT: COMPLIANT");
+            return;
+        }
+        "verify" => {}
+        _ => usage_error(USAGE),
+    }
+
+    let view = PiSignalView::new(&bytes).unwrap_or_else(|e| scxq2_fail(e));
+    view.verify().unwrap_or_else(|e| scxq2_fail(e));
+    let collapse = view.collapse();
+
+    if opts.quiet {
+        return;
+    }
+    let signal = view.to_signal();
+    if opts.json {
+        let collapse = match &collapse {
+            Ok(value) => format!("{:?}", value),
+            Err(_) => "null".to_string(),
+        };
+        println!(
+            "{{\"result\":\"COMPLIANT\",\"lane\":\"{}\",\"lane_hash\":\"{}\",\"orientation\":{},\"closed\":{},\"collapse\":{},\"vector\":{}}}",
+            pi_signal::LANE_ID,
+            view.lane_hash(),
+            signal.orientation,
+            signal.closed,
+            collapse,
+            COMPLIANCE_VECTOR
+        );
+        return;
+    }
+    println!("✔ Magic / Version / Flags");
+    println!("✔ Alignment ({} bytes)", pi_signal::ALIGNMENT);
+    println!("✔ Orientation ({:+})", signal.orientation);
+    println!("✔ Footer Hash ({})", pi_signal::FOOTER_HASH.tag());
+    match collapse {
+        Ok(value) => println!("✔ Collapse = {:?}", value),
+        Err(_) => println!("• Open lane (collapse illegal)"),
+    }
+    println!("\nRESULT: COMPLIANT");
+}
+
+fn run_determinism(args: Vec<String>) {
+    let (target, opts, axioms_dir) = parse_args(args);
+    if axioms_dir.is_some() {
+        usage_error("usage error: --axioms does not apply to determinism");
+    }
+
+    let schema_fail =
+        |reason: String| -> ! { fail(1, &format!("schema validation failure: {}", reason), opts) };
+    let recordings = if target.is_dir() {
+        let signals: Vec<PathBuf> = collect_files(&target)
+            .into_iter()
+            .filter(|p| pi_adapter::is_signal(p))
+            .collect();
+        if signals.is_empty() {
+            schema_fail("no adapter outputs".to_string());
+        }
+        signals
+            .iter()
+            .map(|path| {
+                pi_determinism::from_signal(&read_bytes(path, opts), &path.to_string_lossy())
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_or_else(|e| schema_fail(e))
+    } else if pi_adapter::is_signal(&target) {
+        let recording =
+            pi_determinism::from_signal(&read_bytes(&target, opts), &target.to_string_lossy())
+                .unwrap_or_else(|e| schema_fail(e));
+        vec![recording]
+    } else {
+        pi_determinism::from_recordings(&read_bytes(&target, opts), &target.to_string_lossy())
+            .unwrap_or_else(|e| schema_fail(e))
+    };
+
+    let divergences = pi_determinism::check(&recordings);
+    let describe = |d: &pi_determinism::Divergence| {
+        format!(
+            "nondeterministic adapter {} {} on {}: {} distinct outputs ({}); differing {}",
+            d.key.adapter,
+            d.key.adapter_version,
+            d.key.source_hash,
+            d.outputs.len(),
+            d.outputs
+                .iter()
+                .map(|o| o.join(", "))
+                .collect::<Vec<_>>()
+                .join(" | "),
+            d.fields.join(", ")
+        )
+    };
+    if let Some((last, rest)) = divergences.split_last() {
+        if !opts.json {
+            for divergence in rest {
+                eprintln!("✘ {}", describe(divergence));
+            }
+        }
+        fail(
+            3,
+            &format!("constraint violation: {}", describe(last)),
+            opts,
+        );
+    }
+
+    if opts.quiet {
+        return;
+    }
+    let groups = pi_determinism::group_count(&recordings);
+    if opts.json {
+        println!(
+            "{{\"result\":\"COMPLIANT\",\"target\":\"{}\",\"recordings\":{},\"groups\":{},\"vector\":{}}}",
+            json_escape(&target.to_string_lossy()),
+            recordings.len(),
+            groups,
+            COMPLIANCE_VECTOR
+        );
+        return;
+    }
+    println!(
+        "✔ {} adapter outputs valid ({})",
+        recordings.len(),
+        pi_adapter::SIGNAL_TYPE
+    );
+    println!(
+        "✔ {} (source_hash, adapter, adapter_version) groups deterministic",
+        groups
+    );
+    println!("\nRESULT: COMPLIANT");
+}
+
+fn run_engine(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut lane: Option<PathBuf> = None;
+    let mut name: Option<String> = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            "--invariant" => {
+                name = Some(
+                    args.next()
+                        .unwrap_or_else(|| usage_error("usage error: option requires a value")),
+                )
+            }
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ if lane.is_none() => lane = Some(PathBuf::from(arg)),
+            _ => usage_error("usage error: too many targets"),
+        }
+    }
+    let lane = lane.unwrap_or_else(|| usage_error("usage error: missing target"));
+    let name = name.unwrap_or_else(|| usage_error("usage error: missing --invariant"));
+    let invariant = pi_gccp::invariant(&name).unwrap_or_else(|| {
+        usage_error("usage error: --invariant must be half-turn, mobius or saddle")
+    });
+
+    let bytes = read_bytes(&lane, opts);
+    PiSignalView::new(&bytes)
+        .and_then(|view| view.verify())
+        .unwrap_or_else(|e| fail(5, &format!("SCXQ2 failure: {}", e), opts));
+    let run = pi_gccp::run(&bytes, invariant)
+        .unwrap_or_else(|e| fail(3, &format!("constraint violation: {}", e), opts));
+
+    if opts.quiet {
+        return;
+    }
+    print!("{}", run.render());
+}
+
+fn run_engine_conformance(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut document: Option<PathBuf> = None;
+    for arg in args {
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ if document.is_none() => document = Some(PathBuf::from(arg)),
+            _ => usage_error("usage error: too many targets"),
+        }
+    }
+    let document = document.unwrap_or_else(|| usage_error("usage error: missing target"));
+    let requirements = pi_conformance::Requirements::parse(&read_bytes(&document, opts))
+        .unwrap_or_else(|e| {
+            fail(
+                1,
+                &format!("schema validation failure: {} ({})", e, document.display()),
+                opts,
+            )
+        });
+
+    let certificate = pi_conformance::certify(&requirements, &pi_conformance::Reference);
+    if !opts.quiet {
+        print!("{}", certificate.render());
+    }
+    if let Some(failed) = certificate
+        .results
+        .iter()
+        .find(|r| r.result != pi_conformance::Verdict::Pass)
+    {
+        let msg = format!(
+            "constraint violation: {} {} ({})",
+            failed.test,
+            failed.result,
+            failed.detail.as_deref().unwrap_or("-")
+        );
+        fail(3, &msg, opts);
+    }
+}
+
+fn run_chain(mut args: Vec<String>) {
+    const USAGE: &str = "usage error: chain requires build or verify";
+    if args.is_empty() {
+        usage_error(USAGE);
+    }
+    let command = args.remove(0);
+    let mut opts = Options::new();
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    let scxq2_fail = |reason: String| -> ! { fail(5, &format!("SCXQ2 failure: {}", reason), opts) };
+    let read_lanes = |paths: &[PathBuf]| -> Vec<Vec<u8>> {
+        paths
+            .iter()
+            .map(|path| {
+                let bytes = read_bytes(path, opts);
+                PiSignalView::new(&bytes)
+                    .and_then(|view| view.verify())
+                    .unwrap_or_else(|e| scxq2_fail(format!("{} ({})", e, path.display())));
+                bytes
+            })
+            .collect()
+    };
+
+    match command.as_str() {
+        "build" => {
+            if paths.is_empty() {
+                usage_error("usage error: missing target");
+            }
+            let chain = Chain::fold(&read_lanes(&paths))
+                .unwrap_or_else(|e| fail(3, &format!("constraint violation: {}", e), opts));
+            print!("{}", chain.render());
+        }
+        "verify" => {
+            if paths.len() < 2 {
+                usage_error("usage error: chain verify <chain-proof> <lane>...");
+            }
+            let proof = read_bytes(&paths[0], opts);
+            let chain =
+                Chain::verify(&proof, &read_lanes(&paths[1..])).unwrap_or_else(|e| scxq2_fail(e));
+            if opts.quiet {
+                return;
+            }
+            if opts.json {
+                println!(
+                    "{{\"result\":\"COMPLIANT\",\"proof\":\"{}\",\"links\":{},\"root\":\"{}\",\"vector\":{}}}",
+                    pi_chain::PROOF_TYPE,
+                    chain.links.len(),
+                    chain.root(),
+                    COMPLIANCE_VECTOR
+                );
+                return;
+            }
+            for (i, link) in chain.links.iter().enumerate() {
+                println!("✔ link {} {}", i, link.digest);
+            }
+            match chain.value() {
+                Some(value) => println!("✔ collapse {:?}", value),
+                None => println!("• saddle preserved (non-collapsible)"),
+            }
+            println!("✔ root {}", chain.root());
+            println!("\nRESULT: COMPLIANT");
+        }
+        _ => usage_error(USAGE),
+    }
+}
+
+fn run_split(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut digits: usize = 50;
+    let mut terms: Option<u64> = None;
+    let mut cm1 = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--quiet" => opts.quiet = true,
+            "--cm1" => cm1 = true,
+            "--digits" | "--terms" => {
+                let value = args
+                    .next()
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .unwrap_or_else(|| usage_error("usage error: option requires a number"));
+                if arg == "--digits" {
+                    digits = value as usize;
+                } else if value == 0 {
+                    usage_error("usage error: --terms must be at least 1");
+                } else {
+                    terms = Some(value);
+                }
+            }
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ => usage_error("usage error: split takes no targets"),
+        }
+    }
+    // log2(10) < 3.322; four guard bits cover the truncation.
+    let terms = terms
+        .unwrap_or_else(|| binary_split::terms_for_bits((digits as u64 * 3322).div_ceil(1000) + 4));
+
+    let evaluation = binary_split::evaluate(terms)
+        .unwrap_or_else(|e| fail(2, &format!("CM-1 violation: {}", e), opts));
+    if opts.quiet {
+        return;
+    }
+    if cm1 {
+        println!("{}", scxq2_pack::render_hex(&evaluation.cm1));
+        return;
+    }
+    let rendered = evaluation
+        .render(digits)
+        .unwrap_or_else(|e| fail(3, &format!("constraint violation: {}", e), opts));
+    print!("{}", rendered);
+}
+
+fn run_step(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut target: Option<PathBuf> = None;
+    let mut events: Vec<String> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--quiet" => opts.quiet = true,
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ if target.is_none() => target = Some(PathBuf::from(arg)),
+            _ => events.push(arg),
+        }
+    }
+    let target = target.unwrap_or_else(|| usage_error("usage error: missing target"));
+    let object = Object::parse(&read_bytes(&target, opts)).unwrap_or_else(|e| {
+        fail(
+            1,
+            &format!("schema validation failure: {} ({})", e, target.display()),
+            opts,
+        )
+    });
+
+    // A rejected event leaves the state as it was; later events still run.
+    let mut executor = Executor::new(&object);
+    for event in &events {
+        let _ = executor.apply(event);
+    }
+    if !opts.quiet {
+        print!("{}", executor.render());
+    }
+    if let Some(rejection) = executor.rejected().first() {
+        fail(3, &format!("constraint violation: {}", rejection), opts);
+    }
+}
+
+fn run_idb_write(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut target: Option<PathBuf> = None;
+    let mut out: Option<PathBuf> = None;
+    let mut events: Vec<String> = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--quiet" => opts.quiet = true,
+            "--out" => {
+                let value = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("usage error: option requires a value"));
+                out = Some(PathBuf::from(value));
+            }
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ if target.is_none() => target = Some(PathBuf::from(arg)),
+            _ => events.push(arg),
+        }
+    }
+    let target = target.unwrap_or_else(|| usage_error("usage error: missing target"));
+    let object = Object::parse(&read_bytes(&target, opts)).unwrap_or_else(|e| {
+        fail(
+            1,
+            &format!("schema validation failure: {} ({})", e, target.display()),
+            opts,
+        )
+    });
+
+    let mut executor = Executor::new(&object);
+    for event in &events {
+        if let Err(rejection) = executor.apply(event) {
+            fail(3, &format!("constraint violation: {}", rejection), opts);
+        }
+    }
+    let rendered = Idb::project(&executor)
+        .map(|idb| idb.render())
+        .unwrap_or_else(|e| fail(4, &format!("IDB anchoring failure: {}", e), opts));
+    match out {
+        Some(path) => {
+            fs::write(&path, &rendered).unwrap_or_else(|_| {
+                fail(
+                    4,
+                    &format!("IDB anchoring failure: cannot write {}", path.display()),
+                    opts,
+                )
+            });
+            if !opts.quiet {
+                println!(
+                    "✔ {} steps written ({})",
+                    executor.log().len(),
+                    path.display()
+                );
+            }
+        }
+        None if !opts.quiet => print!("{}", rendered),
+        None => {}
+    }
+}
+
+fn run_replay(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => opts.json = true,
+            "--quiet" => opts.quiet = true,
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    let [ledger, definition] = &paths[..] else {
+        usage_error("usage error: replay requires <IDB.xml> <object.json>");
+    };
+    let idb = Idb::parse(&read_bytes(ledger, opts)).unwrap_or_else(|e| {
+        fail(
+            1,
+            &format!("schema validation failure: {} ({})", e, ledger.display()),
+            opts,
+        )
+    });
+    let object = Object::parse(&read_bytes(definition, opts)).unwrap_or_else(|e| {
+        fail(
+            1,
+            &format!(
+                "schema validation failure: {} ({})",
+                e,
+                definition.display()
+            ),
+            opts,
+        )
+    });
+    let executor = idb.replay(&object).unwrap_or_else(|e| {
+        fail(
+            4,
+            &format!(
+                "IDB anchoring failure: replay diverges: {} ({})",
+                e,
+                ledger.display()
+            ),
+            opts,
+        )
+    });
+
+    if opts.quiet {
+        return;
+    }
+    if opts.json {
+        println!(
+            "{{\"result\":\"COMPLIANT\",\"object\":\"{}\",\"steps\":{},\"state\":\"{}\",\"vector\":{}}}",
+            json_escape(&object.object_id),
+            executor.log().len(),
+            idb.state,
+            COMPLIANCE_VECTOR
+        );
+        return;
+    }
+    println!(
+        "✔ {} causal steps replayed from {}",
+        executor.log().len(),
+        object.object_id
+    );
+    println!("✔ head state {}", idb.state);
+    println!("\nRESULT: COMPLIANT");
+}
+
+fn run_idb_diff(args: Vec<String>) {
+    let mut opts = Options::new();
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--quiet" => opts.quiet = true,
+            _ if arg.starts_with("--") => usage_error("usage error: unknown option"),
+            _ => paths.push(PathBuf::from(arg)),
+        }
+    }
+    let (old, new, definition) = match &paths[..] {
+        [old, new] => (old, new, None),
+        [old, new, definition] => (old, new, Some(definition)),
+        _ => usage_error("usage error: idb-diff requires <old.xml> <new.xml> [<object.json>]"),
+    };
+    let load = |path: &PathBuf| -> Idb {
+        let idb = Idb::parse(&read_bytes(path, opts)).unwrap_or_else(|e| {
+            fail(
+                1,
+                &format!("schema validation failure: {} ({})", e, path.display()),
+                opts,
+            )
+        });
+        idb.check_continuity().unwrap_or_else(|e| {
+            let msg = format!(
+                "IDB anchoring failure: hash continuity violation: {} ({})",
+                e,
+                path.display()
+            );
+            fail(4, &msg, opts)
+        });
+        idb
+    };
+    let object = definition.map(|definition| {
+        Object::parse(&read_bytes(definition, opts)).unwrap_or_else(|e| {
+            fail(
+                1,
+                &format!(
+                    "schema validation failure: {} ({})",
+                    e,
+                    definition.display()
+                ),
+                opts,
+            )
+        })
+    });
+    let diff = idb_diff::diff(&load(old), &load(new), object.as_ref());
+
+    if !opts.quiet {
+        print!("{}", diff.render());
+    }
+    if let Some(change) = diff.illegal() {
+        let msg = format!(
+            "IDB anchoring failure: ledger update is not append-only: {} {} ({})",
+            change.kind, change.subject, change.detail
+        );
+        fail(4, &msg, opts);
+    }
+    if let Some(change) = diff.unverified() {
+        let msg = format!(
+            "IDB anchoring failure: head state move is unverified: {} (pass <object.json> to replay it)",
+            change.subject
+        );
+        fail(4, &msg, opts);
+    }
+}
+
 fn main() {
-    let (target, opts) = parse_args();
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        Some("registry") => return run_registry(args[1..].to_vec()),
+        Some("axioms") => return run_axioms(args[1..].to_vec()),
+        Some("pack") => return run_pack(args[1..].to_vec()),
+        Some("aggregate") => return run_aggregate(args[1..].to_vec()),
+        Some("pi-signal") => return run_pi_signal(args[1..].to_vec()),
+        Some("determinism") => return run_determinism(args[1..].to_vec()),
+        Some("engine") => return run_engine(args[1..].to_vec()),
+        Some("engine-conformance") => return run_engine_conformance(args[1..].to_vec()),
+        Some("chain") => return run_chain(args[1..].to_vec()),
+        Some("split") => return run_split(args[1..].to_vec()),
+        Some("bindings") => return run_bindings(args[1..].to_vec()),
+        Some("conformance") => return run_conformance(args[1..].to_vec()),
+        Some("profile") => return run_profile(args[1..].to_vec()),
+        Some("step") => return run_step(args[1..].to_vec()),
+        Some("idb-write") => return run_idb_write(args[1..].to_vec()),
+        Some("replay") => return run_replay(args[1..].to_vec()),
+        Some("idb-diff") => return run_idb_diff(args[1..].to_vec()),
+        _ => {}
+    }
+
+    let (target, opts, axioms_dir) = parse_args(args);
     let target_type = detect_target_type(&target);
 
-    load_axioms(opts);
+    let axioms = load_axioms(resolve_axioms(&target, axioms_dir, opts), opts);
     schema_validation(&target, target_type, opts);
     cm1_legality(&target, target_type, opts);
-    constraint_integrity(&target, target_type, opts);
+    constraint_integrity(&target, target_type, &axioms, opts);
     idb_anchoring(&target, target_type, opts);
     scxq2_packing(&target, target_type, opts);
 
@@ -497,13 +2049,20 @@ fn main() {
 
     let mut manifest_hash = None;
     if opts.emit_manifest {
-        manifest_hash = Some(emit_manifest(&target, target_type, &target_hash, opts));
+        manifest_hash = Some(emit_manifest(
+            &target,
+            target_type,
+            &target_hash,
+            &axioms,
+            opts,
+        ));
     }
 
     if opts.emit_badges {
-        let hash = manifest_hash.unwrap_or_else(|| emit_manifest(&target, target_type, &target_hash, opts));
+        let hash = manifest_hash
+            .unwrap_or_else(|| emit_manifest(&target, target_type, &target_hash, &axioms, opts));
         emit_badges(&hash, opts);
     }
 
-    emit_success(opts, &target, target_type, &target_hash);
+    emit_success(opts, &target, target_type, &target_hash, &axioms);
 }
@@ -0,0 +1,280 @@
+// scxq7_cse.rs
+// SCXQ7 Causal Step Executor (SCXQ7-CSE/1)
+// Version: v1
+//
+// Runs the causal-state loop of docs/scxq7-causal-state-engine.md over a
+// scxq7.object.v1 object: receive an event, check the `validate` clauses of the
+// step it causes against the current state, apply the step's effects to a
+// candidate state, check every @state invariant against the candidate, then
+// commit a causal step or reject. A rejection leaves the state untouched.
+//
+// Time is logical: the n-th committed step has time n. Each step record carries
+// the cause hash (the event name), the effect hash (the changed fields and
+// their new values) and the hash of the state it commits, all blake3 over the
+// canonical form `field=value\n` in field order. The proof hash binds the step
+// to its parent state:
+//
+//   blake3("parent=<hash>\nstep=<name>\ncause=<hash>\neffect=<hash>\nstate=<hash>\n")
+
+use std::fmt;
+
+use serde_json::Value;
+
+use crate::expr::{self, State};
+use crate::hash::{Algorithm, Digest};
+use crate::scxq7_object::{Object, Op, Scalar, Step};
+
+pub const ENGINE: &str = "SCXQ7-CSE/1";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CausalStep {
+    pub time: u64,
+    pub step: String,
+    pub event: String,
+    pub cause: Digest,
+    pub effect: Digest,
+    pub state: Digest,
+    /// State hash before the step.
+    pub parent: Digest,
+    pub proof: Digest,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// Logical time of the state the event was rejected against.
+    pub time: u64,
+    pub event: String,
+    pub reason: String,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at t={}: {}", self.event, self.time, self.reason)
+    }
+}
+
+fn quoted(text: &str) -> String {
+    Value::String(text.to_string()).to_string()
+}
+
+/// Canonical form of `fields` from `state`, `field=value\n` in field order.
+fn canonical<'a>(state: &State, fields: impl Iterator<Item = &'a String>) -> Vec<u8> {
+    fields
+        .map(|field| format!("{}={}\n", field, state[field]))
+        .collect::<String>()
+        .into_bytes()
+}
+
+/// Canonical bytes of a whole state; `state_hash` is their blake3.
+pub fn encode_state(state: &State) -> Vec<u8> {
+    canonical(state, state.keys())
+}
+
+pub fn state_hash(state: &State) -> Digest {
+    Digest::of(Algorithm::Blake3, &encode_state(state))
+}
+
+fn apply(current: &Scalar, op: Op, operand: &Scalar) -> Result<Scalar, String> {
+    match (op, current, operand) {
+        (Op::Set, _, _) => Ok(operand.clone()),
+        (Op::Add, Scalar::Int(a), Scalar::Int(b)) => a
+            .checked_add(*b)
+            .map(Scalar::Int)
+            .ok_or_else(|| "int overflow".to_string()),
+        (Op::Sub, Scalar::Int(a), Scalar::Int(b)) => a
+            .checked_sub(*b)
+            .map(Scalar::Int)
+            .ok_or_else(|| "int overflow".to_string()),
+        (Op::Add | Op::Sub, Scalar::Float(a), Scalar::Float(b)) => {
+            let x = if op == Op::Add { a + b } else { a - b };
+            if x.is_finite() {
+                Ok(Scalar::Float(x))
+            } else {
+                Err("float result is not finite".to_string())
+            }
+        }
+        _ => Err("operator does not apply to the field type".to_string()),
+    }
+}
+
+pub struct Executor<'a> {
+    object: &'a Object,
+    state: State,
+    log: Vec<CausalStep>,
+    rejected: Vec<Rejection>,
+}
+
+impl<'a> Executor<'a> {
+    /// Starts from the declared initial values at time 0.
+    pub fn new(object: &'a Object) -> Executor<'a> {
+        Executor {
+            object,
+            state: object
+                .state
+                .iter()
+                .map(|(name, field)| (name.clone(), field.initial.clone()))
+                .collect(),
+            log: Vec::new(),
+            rejected: Vec::new(),
+        }
+    }
+
+    pub fn object(&self) -> &'a Object {
+        self.object
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn log(&self) -> &[CausalStep] {
+        &self.log
+    }
+
+    pub fn rejected(&self) -> &[Rejection] {
+        &self.rejected
+    }
+
+    pub fn time(&self) -> u64 {
+        self.log.len() as u64
+    }
+
+    pub fn state_hash(&self) -> Digest {
+        state_hash(&self.state)
+    }
+
+    /// Applies `event`. On rejection the state and log are unchanged and the
+    /// rejection is recorded.
+    pub fn apply(&mut self, event: &str) -> Result<&CausalStep, Rejection> {
+        match self.candidate(event) {
+            Ok((step, candidate)) => {
+                let parent = self.state_hash();
+                let cause = Digest::of(Algorithm::Blake3, event.as_bytes());
+                let effect = Digest::of(
+                    Algorithm::Blake3,
+                    &canonical(&candidate, step.effect.keys()),
+                );
+                let state = state_hash(&candidate);
+                let proof = Digest::of(
+                    Algorithm::Blake3,
+                    format!(
+                        "parent={}\nstep={}\ncause={}\neffect={}\nstate={}\n",
+                        parent, step.name, cause, effect, state
+                    )
+                    .as_bytes(),
+                );
+                let record = CausalStep {
+                    time: self.time() + 1,
+                    step: step.name.clone(),
+                    event: event.to_string(),
+                    cause,
+                    effect,
+                    state,
+                    parent,
+                    proof,
+                };
+                self.state = candidate;
+                self.log.push(record);
+                Ok(&self.log[self.log.len() - 1])
+            }
+            Err(reason) => {
+                let rejection = Rejection {
+                    time: self.time(),
+                    event: event.to_string(),
+                    reason,
+                };
+                self.rejected.push(rejection.clone());
+                Err(rejection)
+            }
+        }
+    }
+
+    /// Validates `event` and builds the candidate state without committing it.
+    fn candidate(&self, event: &str) -> Result<(&'a Step, State), String> {
+        let step = self
+            .object
+            .steps
+            .iter()
+            .find(|step| step.cause == event)
+            .ok_or_else(|| format!("no step is caused by {}", event))?;
+        for clause in &step.validate {
+            if !expr::holds(clause, &self.state)? {
+                return Err(format!("{}: validate {} fails", step.name, clause));
+            }
+        }
+
+        let mut candidate = self.state.clone();
+        for (field, effect) in &step.effect {
+            let value = apply(&candidate[field], effect.op, &effect.operand)
+                .map_err(|e| format!("{}: {}: {}", step.name, field, e))?;
+            candidate.insert(field.clone(), value);
+        }
+        for (field, spec) in &self.object.state {
+            for invariant in &spec.invariants {
+                if !expr::holds(invariant, &candidate)? {
+                    return Err(format!(
+                        "{}: invariant {} of {} fails",
+                        step.name, invariant, field
+                    ));
+                }
+            }
+        }
+        Ok((step, candidate))
+    }
+
+    pub fn render(&self) -> String {
+        let steps = self
+            .log
+            .iter()
+            .map(|s| {
+                format!(
+                    "    {{\"time\": {}, \"step\": {}, \"event\": {}, \"cause\": \"{}\", \"effect\": \"{}\", \"state\": \"{}\", \"proof\": \"{}\"}}",
+                    s.time,
+                    quoted(&s.step),
+                    quoted(&s.event),
+                    s.cause,
+                    s.effect,
+                    s.state,
+                    s.proof
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        let rejected = self
+            .rejected
+            .iter()
+            .map(|r| {
+                format!(
+                    "    {{\"time\": {}, \"event\": {}, \"reason\": {}}}",
+                    r.time,
+                    quoted(&r.event),
+                    quoted(&r.reason)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+        let state = self
+            .state
+            .iter()
+            .map(|(field, value)| format!("{}: {}", quoted(field), value))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let list = |items: String| {
+            if items.is_empty() {
+                "[]".to_string()
+            } else {
+                format!("[\n{}\n  ]", items)
+            }
+        };
+        format!(
+            "{{\n  \"@engine\": \"{}\",\n  \"object\": {},\n  \"steps\": {},\n  \"rejected\": {},\n  \"time\": {},\n  \"state\": {{{}}},\n  \"state_hash\": \"{}\"\n}}\n",
+            ENGINE,
+            quoted(&self.object.object_id),
+            list(steps),
+            list(rejected),
+            self.time(),
+            state,
+            self.state_hash()
+        )
+    }
+}
@@ -0,0 +1,491 @@
+// scxq7_object.rs
+// SCXQ7 Object Model (scxq7.object.v1)
+// Version: v1
+//
+// Typed loader for scxq7.object.v1 objects such as counter.service.json:
+// identity, typed state with invariants, capabilities, causal steps, queries,
+// projections and the collapse mode. Structure is checked, not behaviour: a
+// step may only mutate fields in `mutate`, a query may only read fields in
+// `read`, every projection route resolves to a query or an `emit` literal, and
+// every cause is an event name that triggers exactly one step. Invariants and
+// validate clauses must parse and typecheck as bool over the @state fields
+// (expr.rs). Errors name the offending JSON pointer.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use crate::expr;
+
+pub const OBJECT_SCHEMA: &str = "scxq7.object.v1";
+pub const EVENT_PREFIX: &str = "event.";
+
+const TOP_FIELDS: [&str; 8] = [
+    "@schema",
+    "@identity",
+    "@state",
+    "@capabilities",
+    "@steps",
+    "@queries",
+    "@projections",
+    "@collapse",
+];
+const IDENTITY_FIELDS: [&str; 2] = ["object_id", "class"];
+const STATE_FIELDS: [&str; 3] = ["type", "initial", "invariants"];
+const CAPABILITY_FIELDS: [&str; 3] = ["read", "mutate", "project"];
+const STEP_FIELDS: [&str; 4] = ["@step", "cause", "validate", "effect"];
+const COLLAPSE_FIELDS: [&str; 2] = ["mode", "proof"];
+const ROUTE_METHODS: [&str; 4] = ["GET", "POST", "PUT", "DELETE"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    Int,
+    Float,
+    Bool,
+    String,
+}
+
+impl Type {
+    pub fn parse(name: &str) -> Option<Type> {
+        match name {
+            "int" => Some(Type::Int),
+            "float" => Some(Type::Float),
+            "bool" => Some(Type::Bool),
+            "string" => Some(Type::String),
+            _ => None,
+        }
+    }
+
+    pub fn is_numeric(self) -> bool {
+        matches!(self, Type::Int | Type::Float)
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Int => write!(f, "int"),
+            Type::Float => write!(f, "float"),
+            Type::Bool => write!(f, "bool"),
+            Type::String => write!(f, "string"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scalar {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl Scalar {
+    /// Reads `value` as a scalar of type `ty`; ints are not floats and vice versa.
+    pub fn from_json(value: &Value, ty: Type) -> Option<Scalar> {
+        match ty {
+            Type::Int => value.as_i64().map(Scalar::Int),
+            Type::Float => value.as_f64().filter(|x| x.is_finite()).map(Scalar::Float),
+            Type::Bool => value.as_bool().map(Scalar::Bool),
+            Type::String => value.as_str().map(|s| Scalar::Text(s.to_string())),
+        }
+    }
+}
+
+/// JSON literal form: strings are quoted and escaped.
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Scalar::Int(n) => write!(f, "{}", n),
+            Scalar::Float(x) => write!(f, "{:?}", x),
+            Scalar::Bool(b) => write!(f, "{}", b),
+            Scalar::Text(s) => write!(f, "{}", Value::String(s.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub ty: Type,
+    pub initial: Scalar,
+    pub invariants: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Set,
+    Add,
+    Sub,
+}
+
+impl Op {
+    fn parse(symbol: &str) -> Option<Op> {
+        match symbol {
+            "=" => Some(Op::Set),
+            "+" => Some(Op::Add),
+            "-" => Some(Op::Sub),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Effect {
+    pub op: Op,
+    pub operand: Scalar,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub name: String,
+    pub cause: String,
+    pub validate: Vec<String>,
+    /// Field → effect, in field order.
+    pub effect: BTreeMap<String, Effect>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Projection {
+    Query(String),
+    Emit(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Object {
+    pub object_id: String,
+    pub class: String,
+    pub state: BTreeMap<String, Field>,
+    pub read: Vec<String>,
+    pub mutate: Vec<String>,
+    pub project: Vec<String>,
+    pub steps: Vec<Step>,
+    /// Query name → fields read.
+    pub queries: BTreeMap<String, Vec<String>>,
+    /// Channel → route → projection.
+    pub projections: BTreeMap<String, BTreeMap<String, Projection>>,
+    pub collapse_mode: String,
+    pub collapse_proof: bool,
+}
+
+/// True for `.json` files that declare `@schema: scxq7.object.v1`.
+pub fn is_object(path: &Path) -> bool {
+    if path.extension().and_then(|e| e.to_str()) != Some("json") {
+        return false;
+    }
+    fs::read(path)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+        .map(|doc| doc.get("@schema").and_then(Value::as_str) == Some(OBJECT_SCHEMA))
+        .unwrap_or(false)
+}
+
+/// JSON pointer token for a key (RFC 6901).
+fn token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn object<'a>(value: &'a Value, path: &str) -> Result<&'a Map<String, Value>, String> {
+    value.as_object().ok_or_else(|| {
+        format!(
+            "{}: must be an object",
+            if path.is_empty() { "/" } else { path }
+        )
+    })
+}
+
+fn known_fields(map: &Map<String, Value>, path: &str, allowed: &[&str]) -> Result<(), String> {
+    for key in map.keys() {
+        if !allowed.contains(&key.as_str()) {
+            return Err(format!("{}/{}: unknown field", path, token(key)));
+        }
+    }
+    Ok(())
+}
+
+fn required<'a>(map: &'a Map<String, Value>, path: &str, key: &str) -> Result<&'a Value, String> {
+    map.get(key)
+        .ok_or_else(|| format!("{}/{}: missing", path, token(key)))
+}
+
+fn text(value: &Value, path: &str) -> Result<String, String> {
+    value
+        .as_str()
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| format!("{}: must be a non-empty string", path))
+}
+
+fn strings(value: &Value, path: &str) -> Result<Vec<String>, String> {
+    value
+        .as_array()
+        .ok_or_else(|| format!("{}: must be an array", path))?
+        .iter()
+        .enumerate()
+        .map(|(i, item)| text(item, &format!("{}/{}", path, i)))
+        .collect()
+}
+
+/// `event.<name>` with dot-separated lowercase segments.
+pub fn is_event_name(cause: &str) -> bool {
+    cause.strip_prefix(EVENT_PREFIX).is_some_and(|rest| {
+        rest.split('.').all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'_')
+        })
+    })
+}
+
+/// `METHOD /path`.
+fn is_route(route: &str) -> bool {
+    route.split_once(' ').is_some_and(|(method, path)| {
+        ROUTE_METHODS.contains(&method) && path.starts_with('/') && !path.contains(' ')
+    })
+}
+
+impl Object {
+    pub fn parse(bytes: &[u8]) -> Result<Object, String> {
+        let doc: Value =
+            serde_json::from_slice(bytes).map_err(|_| "object is not valid JSON".to_string())?;
+        Object::from_value(&doc)
+    }
+
+    pub fn from_value(doc: &Value) -> Result<Object, String> {
+        let top = object(doc, "")?;
+        known_fields(top, "", &TOP_FIELDS)?;
+        if required(top, "", "@schema")?.as_str() != Some(OBJECT_SCHEMA) {
+            return Err(format!("/@schema: must be {}", OBJECT_SCHEMA));
+        }
+
+        let identity = object(required(top, "", "@identity")?, "/@identity")?;
+        known_fields(identity, "/@identity", &IDENTITY_FIELDS)?;
+        let object_id = text(
+            required(identity, "/@identity", "object_id")?,
+            "/@identity/object_id",
+        )?;
+        let class = text(
+            required(identity, "/@identity", "class")?,
+            "/@identity/class",
+        )?;
+
+        let mut state = BTreeMap::new();
+        for (name, spec) in object(required(top, "", "@state")?, "/@state")? {
+            let path = format!("/@state/{}", token(name));
+            let spec = object(spec, &path)?;
+            known_fields(spec, &path, &STATE_FIELDS)?;
+            let type_name = text(required(spec, &path, "type")?, &format!("{}/type", path))?;
+            let ty = Type::parse(&type_name)
+                .ok_or_else(|| format!("{}/type: unknown type {}", path, type_name))?;
+            let initial = Scalar::from_json(required(spec, &path, "initial")?, ty)
+                .ok_or_else(|| format!("{}/initial: must be {}", path, ty))?;
+            let invariants = match spec.get("invariants") {
+                None => Vec::new(),
+                Some(list) => strings(list, &format!("{}/invariants", path))?,
+            };
+            state.insert(
+                name.clone(),
+                Field {
+                    ty,
+                    initial,
+                    invariants,
+                },
+            );
+        }
+        if state.is_empty() {
+            return Err("/@state: must declare at least one field".to_string());
+        }
+        let types: BTreeMap<String, Type> = state
+            .iter()
+            .map(|(name, field)| (name.clone(), field.ty))
+            .collect();
+        for (name, field) in &state {
+            for (i, invariant) in field.invariants.iter().enumerate() {
+                expr::check_condition(invariant, &types)
+                    .map_err(|e| format!("/@state/{}/invariants/{}: {}", token(name), i, e))?;
+            }
+        }
+
+        let capabilities = object(required(top, "", "@capabilities")?, "/@capabilities")?;
+        known_fields(capabilities, "/@capabilities", &CAPABILITY_FIELDS)?;
+        let capability = |key: &str| match capabilities.get(key) {
+            None => Ok(Vec::new()),
+            Some(list) => strings(list, &format!("/@capabilities/{}", key)),
+        };
+        let (read, mutate, project) = (
+            capability("read")?,
+            capability("mutate")?,
+            capability("project")?,
+        );
+        for (key, fields) in [("read", &read), ("mutate", &mutate)] {
+            for (i, field) in fields.iter().enumerate() {
+                if !state.contains_key(field) {
+                    return Err(format!(
+                        "/@capabilities/{}/{}: {} is not a @state field",
+                        key, i, field
+                    ));
+                }
+            }
+        }
+
+        let mut steps: Vec<Step> = Vec::new();
+        let step_list = required(top, "", "@steps")?
+            .as_array()
+            .ok_or_else(|| "/@steps: must be an array".to_string())?;
+        for (i, step) in step_list.iter().enumerate() {
+            let path = format!("/@steps/{}", i);
+            let spec = object(step, &path)?;
+            known_fields(spec, &path, &STEP_FIELDS)?;
+            let name = text(required(spec, &path, "@step")?, &format!("{}/@step", path))?;
+            if steps.iter().any(|s| s.name == name) {
+                return Err(format!("{}/@step: duplicate step {}", path, name));
+            }
+            let cause = text(required(spec, &path, "cause")?, &format!("{}/cause", path))?;
+            if !is_event_name(&cause) {
+                return Err(format!(
+                    "{}/cause: {} is not an event name ({}<name>)",
+                    path, cause, EVENT_PREFIX
+                ));
+            }
+            if steps.iter().any(|s| s.cause == cause) {
+                return Err(format!("{}/cause: duplicate cause {}", path, cause));
+            }
+            let validate = match spec.get("validate") {
+                None => Vec::new(),
+                Some(list) => strings(list, &format!("{}/validate", path))?,
+            };
+            for (j, clause) in validate.iter().enumerate() {
+                expr::check_condition(clause, &types)
+                    .map_err(|e| format!("{}/validate/{}: {}", path, j, e))?;
+            }
+
+            let effect_path = format!("{}/effect", path);
+            let mut effect = BTreeMap::new();
+            for (field, change) in object(required(spec, &path, "effect")?, &effect_path)? {
+                let here = format!("{}/{}", effect_path, token(field));
+                if !mutate.contains(field) {
+                    return Err(format!(
+                        "{}: {} is not in @capabilities/mutate",
+                        here, field
+                    ));
+                }
+                let ty = state[field].ty;
+                let change = object(change, &here)?;
+                let [(symbol, operand)] = change.iter().collect::<Vec<_>>()[..] else {
+                    return Err(format!("{}: must hold exactly one operator", here));
+                };
+                let op = Op::parse(symbol)
+                    .ok_or_else(|| format!("{}/{}: unknown operator", here, token(symbol)))?;
+                if op != Op::Set && !ty.is_numeric() {
+                    return Err(format!(
+                        "{}/{}: {} is not numeric",
+                        here,
+                        token(symbol),
+                        field
+                    ));
+                }
+                let operand = Scalar::from_json(operand, ty)
+                    .ok_or_else(|| format!("{}/{}: operand must be {}", here, token(symbol), ty))?;
+                effect.insert(field.clone(), Effect { op, operand });
+            }
+            if effect.is_empty() {
+                return Err(format!("{}: must change at least one field", effect_path));
+            }
+            steps.push(Step {
+                name,
+                cause,
+                validate,
+                effect,
+            });
+        }
+
+        let mut queries = BTreeMap::new();
+        if let Some(list) = top.get("@queries") {
+            for (name, spec) in object(list, "/@queries")? {
+                let path = format!("/@queries/{}", token(name));
+                let spec = object(spec, &path)?;
+                known_fields(spec, &path, &["read"])?;
+                let reads = required(spec, &path, "read")?;
+                let fields = if reads.is_string() {
+                    vec![text(reads, &format!("{}/read", path))?]
+                } else {
+                    strings(reads, &format!("{}/read", path))?
+                };
+                for field in &fields {
+                    if !read.contains(field) {
+                        return Err(format!(
+                            "{}/read: {} is not in @capabilities/read",
+                            path, field
+                        ));
+                    }
+                }
+                queries.insert(name.clone(), fields);
+            }
+        }
+
+        let mut projections = BTreeMap::new();
+        if let Some(channels) = top.get("@projections") {
+            for (channel, routes) in object(channels, "/@projections")? {
+                let path = format!("/@projections/{}", token(channel));
+                if !project.contains(channel) {
+                    return Err(format!(
+                        "{}: {} is not in @capabilities/project",
+                        path, channel
+                    ));
+                }
+                let mut table = BTreeMap::new();
+                for (route, target) in object(routes, &path)? {
+                    let here = format!("{}/{}", path, token(route));
+                    if !is_route(route) {
+                        return Err(format!("{}: route must be METHOD /path", here));
+                    }
+                    let target = object(target, &here)?;
+                    let projection = match (target.get("query"), target.get("emit")) {
+                        (Some(query), None) if target.len() == 1 => {
+                            let query = text(query, &format!("{}/query", here))?;
+                            if !queries.contains_key(&query) {
+                                return Err(format!("{}/query: unknown query {}", here, query));
+                            }
+                            Projection::Query(query)
+                        }
+                        (None, Some(literal)) if target.len() == 1 => Projection::Emit(
+                            literal
+                                .as_str()
+                                .ok_or_else(|| format!("{}/emit: must be a string literal", here))?
+                                .to_string(),
+                        ),
+                        _ => {
+                            return Err(format!("{}: must hold exactly one of query or emit", here))
+                        }
+                    };
+                    table.insert(route.clone(), projection);
+                }
+                projections.insert(channel.clone(), table);
+            }
+        }
+
+        let collapse = object(required(top, "", "@collapse")?, "/@collapse")?;
+        known_fields(collapse, "/@collapse", &COLLAPSE_FIELDS)?;
+        let collapse_mode = text(required(collapse, "/@collapse", "mode")?, "/@collapse/mode")?;
+        let collapse_proof = required(collapse, "/@collapse", "proof")?
+            .as_bool()
+            .ok_or_else(|| "/@collapse/proof: must be boolean".to_string())?;
+
+        Ok(Object {
+            object_id,
+            class,
+            state,
+            read,
+            mutate,
+            project,
+            steps,
+            queries,
+            projections,
+            collapse_mode,
+            collapse_proof,
+        })
+    }
+}